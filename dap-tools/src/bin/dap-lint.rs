@@ -0,0 +1,79 @@
+//! Checks a recorded message trace for protocol violations.
+//!
+//! Usage: `dap-lint [FILE]`
+//!
+//! The trace has one JSON encoded protocol message per line, in the order they
+//! were sent by the client and the adapter. Lines can also be trace entries as
+//! written by `dap-proxy`, in which case their message is checked. The trace is
+//! read from standard input if no file is given. Exits with status 1 if any
+//! violation is found.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::process::ExitCode;
+
+use dap_types::lint::Linter;
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let (name, input): (&str, Box<dyn BufRead>) = match args.as_slice() {
+        [] => ("<stdin>", Box::new(std::io::stdin().lock())),
+        [path] => match File::open(path) {
+            Ok(file) => (path, Box::new(BufReader::new(file))),
+            Err(e) => {
+                eprintln!("dap-lint: cannot open {path}: {e}");
+                return ExitCode::from(2);
+            }
+        },
+        _ => {
+            eprintln!("usage: dap-lint [FILE]");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut linter = Linter::new();
+    let mut violations = 0;
+    for (i, line) in input.lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("dap-lint: cannot read {name}: {e}");
+                return ExitCode::from(2);
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_number = i + 1;
+        let value = match serde_json::from_str::<serde_json::Value>(&line) {
+            Ok(value) => value,
+            Err(e) => {
+                println!("{name}:{line_number}: invalid JSON: {e}");
+                violations += 1;
+                continue;
+            }
+        };
+        // The message of a trace entry is checked on its own, so that an
+        // invalid message is reported as such rather than as an invalid entry.
+        let value = match value {
+            serde_json::Value::Object(mut entry) if entry.contains_key("direction") => {
+                entry.remove("message").unwrap_or_default()
+            }
+            value => value,
+        };
+        for violation in linter.check_value(value) {
+            println!(
+                "{name}:{line_number}: {:?}: {}",
+                violation.rule, violation.message
+            );
+            violations += 1;
+        }
+    }
+
+    if violations == 0 {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("dap-lint: found {violations} violation(s)");
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,155 @@
+//! Runs a debug adapter and records the messages it exchanges with the client.
+//!
+//! Usage: `dap-proxy --trace FILE [--] ADAPTER [ARGS...]`
+//!
+//! The proxy is started by the client in place of the adapter. It launches the
+//! adapter as a child process and copies the bytes sent between the client (on
+//! the proxy's standard input and output) and the adapter unchanged. Every
+//! message is also written to the trace file, in the format read by
+//! `dap_types::trace::TraceReader`, including messages that are not valid
+//! protocol messages.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::process::{Command, ExitCode, Stdio};
+use std::sync::{Arc, Mutex};
+
+use dap_types::framing::{FramingError, MessageReader};
+use dap_types::trace::{Direction, TraceEntry, TraceWriter};
+use serde_json::Value;
+
+const USAGE: &str = "usage: dap-proxy --trace FILE [--] ADAPTER [ARGS...]";
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let (trace_path, command) = match args.as_slice() {
+        [flag, path, command @ ..] if flag == "--trace" => {
+            let command = command.strip_prefix(&["--".to_owned()]).unwrap_or(command);
+            (path, command)
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let [program, program_args @ ..] = command else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let trace = match File::create(trace_path) {
+        Ok(file) => Arc::new(Mutex::new(TraceWriter::new(file))),
+        Err(e) => {
+            eprintln!("dap-proxy: cannot create {trace_path}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    let mut adapter = match Command::new(program)
+        .args(program_args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+    {
+        Ok(adapter) => adapter,
+        Err(e) => {
+            eprintln!("dap-proxy: cannot start {program}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    let adapter_stdin = adapter.stdin.take().unwrap();
+    let adapter_stdout = adapter.stdout.take().unwrap();
+
+    // The client side is not joined: it may be blocked reading standard input
+    // after the adapter has exited.
+    let client_trace = trace.clone();
+    std::thread::spawn(move || {
+        report(forward(
+            std::io::stdin(),
+            adapter_stdin,
+            Direction::ClientToAdapter,
+            &client_trace,
+        ));
+    });
+    let to_client = std::thread::spawn(move || {
+        report(forward(
+            adapter_stdout,
+            std::io::stdout(),
+            Direction::AdapterToClient,
+            &trace,
+        ));
+    });
+    let _ = to_client.join();
+
+    match adapter.wait() {
+        Ok(status) => ExitCode::from(status.code().unwrap_or(1).clamp(0, 255) as u8),
+        Err(e) => {
+            eprintln!("dap-proxy: cannot wait for adapter: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Copies bytes from `reader` to `writer` until `reader` ends, recording each
+/// message in the trace.
+///
+/// Invalid framing is only reported, since the bytes are copied either way.
+fn forward(
+    reader: impl Read,
+    writer: impl Write,
+    direction: Direction,
+    trace: &Mutex<TraceWriter<File>>,
+) -> Result<(), FramingError> {
+    let mut reader = MessageReader::new(Tee { reader, writer });
+    loop {
+        match reader.read_content() {
+            Ok(Some(content)) => record(trace, direction, &content),
+            Ok(None) => return Ok(()),
+            Err(FramingError::Io(e)) => return Err(FramingError::Io(e)),
+            Err(e) => eprintln!("dap-proxy: invalid {direction:?} message: {e}"),
+        }
+    }
+}
+
+/// Reader that writes everything read through it to `writer`, so that the
+/// bytes are forwarded as they arrive while a copy is parsed for the trace.
+struct Tee<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: Read, W: Write> Read for Tee<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.writer.write_all(&buf[..read])?;
+        self.writer.flush()?;
+        Ok(read)
+    }
+}
+
+fn record(trace: &Mutex<TraceWriter<File>>, direction: Direction, content: &[u8]) {
+    // Messages are recorded as they were sent, so that a trace shows what an
+    // adapter got wrong. Content that is not JSON is recorded as a string.
+    let entry = match serde_json::from_slice::<Value>(content) {
+        Ok(message) => {
+            let entry = TraceEntry::now(direction, message);
+            if let Err(e) = entry.decode() {
+                eprintln!("dap-proxy: invalid {direction:?} message: {e}");
+            }
+            entry
+        }
+        Err(e) => {
+            eprintln!("dap-proxy: cannot decode {direction:?} message: {e}");
+            let content = String::from_utf8_lossy(content).into_owned();
+            TraceEntry::now(direction, Value::String(content))
+        }
+    };
+    if let Err(e) = trace.lock().unwrap().write(&entry) {
+        eprintln!("dap-proxy: cannot write trace: {e}");
+    }
+}
+
+fn report(result: Result<(), FramingError>) {
+    if let Err(e) = result {
+        eprintln!("dap-proxy: {e}");
+    }
+}
@@ -0,0 +1,799 @@
+// This file is autogenerated. Do not edit by hand.
+// To regenerate from schema, run `cargo run -p generator`.
+
+#![allow(clippy::doc_lazy_continuation)]
+
+use std::future::Future;
+
+use crate::capabilities;
+use crate::messages::{Request, Requests, Response};
+use crate::requests::{self, Request as _};
+
+/// Error returned by a [`DebugAdapter`] request handler, sent to the client as an error response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdapterError {
+    /// Short error reason, or a free-form message.
+    pub message: String,
+    /// Structured error for the response body.
+    pub error: Option<Box<crate::Message>>,
+}
+
+/// Result of a [`DebugAdapter`] request handler.
+pub type HandlerResult<T> = Result<T, AdapterError>;
+
+impl AdapterError {
+    pub fn new(message: impl Into<String>) -> Self {
+        AdapterError {
+            message: message.into(),
+            error: None,
+        }
+    }
+
+    pub fn with_error(message: impl Into<String>, error: crate::Message) -> Self {
+        AdapterError {
+            message: message.into(),
+            error: Some(Box::new(error)),
+        }
+    }
+
+    /// Error for a request that the adapter does not implement.
+    pub fn unsupported(command: &str) -> Self {
+        AdapterError::new(format!("unsupported request: {command}"))
+    }
+
+    /// Error for a request whose arguments could not be decoded.
+    pub fn invalid_arguments(error: serde_json::Error) -> Self {
+        AdapterError::new(format!("invalid arguments: {error}"))
+    }
+
+    /// Creates an error response to `request`.
+    pub fn into_response(self, request: &Request) -> Response {
+        Response::error(
+            0,
+            request.seq,
+            request.command.clone(),
+            self.message,
+            self.error.map(|e| *e),
+        )
+    }
+}
+
+impl std::fmt::Display for AdapterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AdapterError {}
+
+pub(crate) fn respond<R: requests::Request>(
+    request: &Request,
+    result: HandlerResult<R::Response>,
+) -> Response {
+    let body = match result {
+        Ok(body) => body,
+        Err(e) => return e.into_response(request),
+    };
+    match Response::success::<R>(0, request.seq, body) {
+        Ok(response) => response,
+        Err(e) => {
+            AdapterError::new(format!("failed to encode response: {e}")).into_response(request)
+        }
+    }
+}
+
+/// Error returned by the default handler of a request, which warns if adapter
+/// `A` claims to handle the request.
+fn not_implemented<A: DebugAdapter + ?Sized>(command: &str) -> AdapterError {
+    if A::HANDLED_REQUESTS.contains(&command) {
+        log::warn!(
+            "`{command}` is listed in `DebugAdapter::HANDLED_REQUESTS`, but its handler is not implemented"
+        );
+    }
+    AdapterError::unsupported(command)
+}
+
+/// Handler for requests sent by the client to a debug adapter.
+///
+/// Every method has a default implementation that responds with an "unsupported request" error, so an adapter only implements the requests it supports.
+/// Requests are dispatched to these methods by [`dispatch`].
+pub trait DebugAdapter: Send {
+    /// Commands of the requests this adapter implements, e.g. `requests::StepBack::COMMAND`.
+    ///
+    /// When set, capabilities returned from [`DebugAdapter::initialize`] that gate these requests are filled in, and a warning is logged for capabilities that disagree with them (see [`crate::capabilities`]).
+    /// A warning is also logged when a listed request reaches its default handler, which means that the handler is not implemented.
+    const HANDLED_REQUESTS: &'static [&'static str] = &[];
+
+    /// The `cancel` request is used by the client in two situations:
+    /// - to indicate that it is no longer interested in the result produced by a specific request issued earlier
+    /// - to cancel a progress sequence.
+    /// Clients should only call this request if the corresponding capability `supportsCancelRequest` is true.
+    /// This request has a hint characteristic: a debug adapter can only be expected to make a 'best effort' in honoring this request but there are no guarantees.
+    /// The `cancel` request may return an error if it could not cancel an operation but a client should refrain from presenting this error to end users.
+    /// The request that got cancelled still needs to send a response back. This can either be a normal result (`success` attribute true) or an error response (`success` attribute false and the `message` set to `cancelled`).
+    /// Returning partial results from a cancelled request is possible but please note that a client has no generic way for detecting that a response is partial or not.
+    /// The progress that got cancelled still needs to send a `progressEnd` event back.
+    ///  A client should not assume that progress just got cancelled after sending the `cancel` request.
+    fn cancel(
+        &mut self,
+        arguments: crate::CancelArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Cancel::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The `initialize` request is sent as the first request from the client to the debug adapter in order to configure it with client capabilities and to retrieve capabilities from the debug adapter.
+    /// Until the debug adapter has responded with an `initialize` response, the client must not send any additional requests or events to the debug adapter.
+    /// In addition the debug adapter is not allowed to send any requests or events to the client until it has responded with an `initialize` response.
+    /// The `initialize` request may only be sent once.
+    fn initialize(
+        &mut self,
+        arguments: crate::InitializeRequestArguments,
+    ) -> impl Future<Output = HandlerResult<crate::Capabilities>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Initialize::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// This request indicates that the client has finished initialization of the debug adapter.
+    /// So it is the last request in the sequence of configuration requests (which was started by the `initialized` event).
+    /// Clients should only call this request if the corresponding capability `supportsConfigurationDoneRequest` is true.
+    fn configuration_done(
+        &mut self,
+        arguments: crate::ConfigurationDoneArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::ConfigurationDone::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// This launch request is sent from the client to the debug adapter to start the debuggee with or without debugging (if `noDebug` is true).
+    /// Since launching is debugger/runtime specific, the arguments for this request are not part of this specification.
+    fn launch(
+        &mut self,
+        arguments: crate::LaunchRequestArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Launch::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The `attach` request is sent from the client to the debug adapter to attach to a debuggee that is already running.
+    /// Since attaching is debugger/runtime specific, the arguments for this request are not part of this specification.
+    fn attach(
+        &mut self,
+        arguments: crate::AttachRequestArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Attach::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Restarts a debug session. Clients should only call this request if the corresponding capability `supportsRestartRequest` is true.
+    /// If the capability is missing or has the value false, a typical client emulates `restart` by terminating the debug adapter first and then launching it anew.
+    fn restart(
+        &mut self,
+        arguments: crate::RestartArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Restart::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The `disconnect` request asks the debug adapter to disconnect from the debuggee (thus ending the debug session) and then to shut down itself (the debug adapter).
+    /// In addition, the debug adapter must terminate the debuggee if it was started with the `launch` request. If an `attach` request was used to connect to the debuggee, then the debug adapter must not terminate the debuggee.
+    /// This implicit behavior of when to terminate the debuggee can be overridden with the `terminateDebuggee` argument (which is only supported by a debug adapter if the corresponding capability `supportTerminateDebuggee` is true).
+    fn disconnect(
+        &mut self,
+        arguments: crate::DisconnectArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Disconnect::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The `terminate` request is sent from the client to the debug adapter in order to shut down the debuggee gracefully. Clients should only call this request if the capability `supportsTerminateRequest` is true.
+    /// Typically a debug adapter implements `terminate` by sending a software signal which the debuggee intercepts in order to clean things up properly before terminating itself.
+    /// Please note that this request does not directly affect the state of the debug session: if the debuggee decides to veto the graceful shutdown for any reason by not terminating itself, then the debug session just continues.
+    /// Clients can surface the `terminate` request as an explicit command or they can integrate it into a two stage Stop command that first sends `terminate` to request a graceful shutdown, and if that fails uses `disconnect` for a forceful shutdown.
+    fn terminate(
+        &mut self,
+        arguments: crate::TerminateArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Terminate::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The `breakpointLocations` request returns all possible locations for source breakpoints in a given range.
+    /// Clients should only call this request if the corresponding capability `supportsBreakpointLocationsRequest` is true.
+    fn breakpoint_locations(
+        &mut self,
+        arguments: crate::BreakpointLocationsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::BreakpointLocationsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::BreakpointLocations::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Sets multiple breakpoints for a single source and clears all previous breakpoints in that source.
+    /// To clear all breakpoint for a source, specify an empty array.
+    /// When a breakpoint is hit, a `stopped` event (with reason `breakpoint`) is generated.
+    fn set_breakpoints(
+        &mut self,
+        arguments: crate::SetBreakpointsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::SetBreakpointsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::SetBreakpoints::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Replaces all existing function breakpoints with new function breakpoints.
+    /// To clear all function breakpoints, specify an empty array.
+    /// When a function breakpoint is hit, a `stopped` event (with reason `function breakpoint`) is generated.
+    /// Clients should only call this request if the corresponding capability `supportsFunctionBreakpoints` is true.
+    fn set_function_breakpoints(
+        &mut self,
+        arguments: crate::SetFunctionBreakpointsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::SetFunctionBreakpointsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::SetFunctionBreakpoints::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request configures the debugger's response to thrown exceptions. Each of the `filters`, `filterOptions`, and `exceptionOptions` in the request are independent configurations to a debug adapter indicating a kind of exception to catch. An exception thrown in a program should result in a `stopped` event from the debug adapter (with reason `exception`) if any of the configured filters match.
+    /// Clients should only call this request if the corresponding capability `exceptionBreakpointFilters` returns one or more filters.
+    fn set_exception_breakpoints(
+        &mut self,
+        arguments: crate::SetExceptionBreakpointsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::SetExceptionBreakpointsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::SetExceptionBreakpoints::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Obtains information on a possible data breakpoint that could be set on an expression or variable.
+    /// Clients should only call this request if the corresponding capability `supportsDataBreakpoints` is true.
+    fn data_breakpoint_info(
+        &mut self,
+        arguments: crate::DataBreakpointInfoArguments,
+    ) -> impl Future<Output = HandlerResult<crate::DataBreakpointInfoResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::DataBreakpointInfo::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Replaces all existing data breakpoints with new data breakpoints.
+    /// To clear all data breakpoints, specify an empty array.
+    /// When a data breakpoint is hit, a `stopped` event (with reason `data breakpoint`) is generated.
+    /// Clients should only call this request if the corresponding capability `supportsDataBreakpoints` is true.
+    fn set_data_breakpoints(
+        &mut self,
+        arguments: crate::SetDataBreakpointsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::SetDataBreakpointsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::SetDataBreakpoints::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Replaces all existing instruction breakpoints. Typically, instruction breakpoints would be set from a disassembly window.
+    /// To clear all instruction breakpoints, specify an empty array.
+    /// When an instruction breakpoint is hit, a `stopped` event (with reason `instruction breakpoint`) is generated.
+    /// Clients should only call this request if the corresponding capability `supportsInstructionBreakpoints` is true.
+    fn set_instruction_breakpoints(
+        &mut self,
+        arguments: crate::SetInstructionBreakpointsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::SetInstructionBreakpointsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::SetInstructionBreakpoints::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request resumes execution of all threads. If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true resumes only the specified thread. If not all threads were resumed, the `allThreadsContinued` attribute of the response should be set to false.
+    fn continue_(
+        &mut self,
+        arguments: crate::ContinueArguments,
+    ) -> impl Future<Output = HandlerResult<crate::ContinueResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Continue::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request executes one step (in the given granularity) for the specified thread and allows all other threads to run freely by resuming them.
+    /// If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true prevents other suspended threads from resuming.
+    /// The debug adapter first sends the response and then a `stopped` event (with reason `step`) after the step has completed.
+    fn next(
+        &mut self,
+        arguments: crate::NextArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Next::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request resumes the given thread to step into a function/method and allows all other threads to run freely by resuming them.
+    /// If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true prevents other suspended threads from resuming.
+    /// If the request cannot step into a target, `stepIn` behaves like the `next` request.
+    /// The debug adapter first sends the response and then a `stopped` event (with reason `step`) after the step has completed.
+    /// If there are multiple function/method calls (or other targets) on the source line,
+    /// the argument `targetId` can be used to control into which target the `stepIn` should occur.
+    /// The list of possible targets for a given source line can be retrieved via the `stepInTargets` request.
+    fn step_in(
+        &mut self,
+        arguments: crate::StepInArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::StepIn::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request resumes the given thread to step out (return) from a function/method and allows all other threads to run freely by resuming them.
+    /// If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true prevents other suspended threads from resuming.
+    /// The debug adapter first sends the response and then a `stopped` event (with reason `step`) after the step has completed.
+    fn step_out(
+        &mut self,
+        arguments: crate::StepOutArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::StepOut::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request executes one backward step (in the given granularity) for the specified thread and allows all other threads to run backward freely by resuming them.
+    /// If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true prevents other suspended threads from resuming.
+    /// The debug adapter first sends the response and then a `stopped` event (with reason `step`) after the step has completed.
+    /// Clients should only call this request if the corresponding capability `supportsStepBack` is true.
+    fn step_back(
+        &mut self,
+        arguments: crate::StepBackArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::StepBack::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request resumes backward execution of all threads. If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true resumes only the specified thread. If not all threads were resumed, the `allThreadsContinued` attribute of the response should be set to false.
+    /// Clients should only call this request if the corresponding capability `supportsStepBack` is true.
+    fn reverse_continue(
+        &mut self,
+        arguments: crate::ReverseContinueArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::ReverseContinue::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request restarts execution of the specified stack frame.
+    /// The debug adapter first sends the response and then a `stopped` event (with reason `restart`) after the restart has completed.
+    /// Clients should only call this request if the corresponding capability `supportsRestartFrame` is true.
+    fn restart_frame(
+        &mut self,
+        arguments: crate::RestartFrameArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::RestartFrame::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request sets the location where the debuggee will continue to run.
+    /// This makes it possible to skip the execution of code or to execute code again.
+    /// The code between the current location and the goto target is not executed but skipped.
+    /// The debug adapter first sends the response and then a `stopped` event with reason `goto`.
+    /// Clients should only call this request if the corresponding capability `supportsGotoTargetsRequest` is true (because only then goto targets exist that can be passed as arguments).
+    fn goto(
+        &mut self,
+        arguments: crate::GotoArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Goto::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request suspends the debuggee.
+    /// The debug adapter first sends the response and then a `stopped` event (with reason `pause`) after the thread has been paused successfully.
+    fn pause(
+        &mut self,
+        arguments: crate::PauseArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Pause::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request returns a stacktrace from the current execution state of a given thread.
+    /// A client can request all stack frames by omitting the startFrame and levels arguments. For performance-conscious clients and if the corresponding capability `supportsDelayedStackTraceLoading` is true, stack frames can be retrieved in a piecemeal way with the `startFrame` and `levels` arguments. The response of the `stackTrace` request may contain a `totalFrames` property that hints at the total number of frames in the stack. If a client needs this total number upfront, it can issue a request for a single (first) frame and depending on the value of `totalFrames` decide how to proceed. In any case a client should be prepared to receive fewer frames than requested, which is an indication that the end of the stack has been reached.
+    fn stack_trace(
+        &mut self,
+        arguments: crate::StackTraceArguments,
+    ) -> impl Future<Output = HandlerResult<crate::StackTraceResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::StackTrace::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request returns the variable scopes for a given stack frame ID.
+    fn scopes(
+        &mut self,
+        arguments: crate::ScopesArguments,
+    ) -> impl Future<Output = HandlerResult<crate::ScopesResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Scopes::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Retrieves all child variables for the given variable reference.
+    /// A filter can be used to limit the fetched children to either named or indexed children.
+    fn variables(
+        &mut self,
+        arguments: crate::VariablesArguments,
+    ) -> impl Future<Output = HandlerResult<crate::VariablesResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Variables::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Set the variable with the given name in the variable container to a new value. Clients should only call this request if the corresponding capability `supportsSetVariable` is true.
+    /// If a debug adapter implements both `setVariable` and `setExpression`, a client will only use `setExpression` if the variable has an `evaluateName` property.
+    fn set_variable(
+        &mut self,
+        arguments: crate::SetVariableArguments,
+    ) -> impl Future<Output = HandlerResult<crate::SetVariableResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::SetVariable::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request retrieves the source code for a given source reference.
+    fn source(
+        &mut self,
+        arguments: crate::SourceArguments,
+    ) -> impl Future<Output = HandlerResult<crate::SourceResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Source::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request retrieves a list of all threads.
+    fn threads(
+        &mut self,
+        arguments: crate::ThreadsArgument,
+    ) -> impl Future<Output = HandlerResult<crate::ThreadsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Threads::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// The request terminates the threads with the given ids.
+    /// Clients should only call this request if the corresponding capability `supportsTerminateThreadsRequest` is true.
+    fn terminate_threads(
+        &mut self,
+        arguments: crate::TerminateThreadsArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::TerminateThreads::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Modules can be retrieved from the debug adapter with this request which can either return all modules or a range of modules to support paging.
+    /// Clients should only call this request if the corresponding capability `supportsModulesRequest` is true.
+    fn modules(
+        &mut self,
+        arguments: crate::ModulesArguments,
+    ) -> impl Future<Output = HandlerResult<crate::ModulesResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Modules::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Retrieves the set of all sources currently loaded by the debugged process.
+    /// Clients should only call this request if the corresponding capability `supportsLoadedSourcesRequest` is true.
+    fn loaded_sources(
+        &mut self,
+        arguments: crate::LoadedSourcesArguments,
+    ) -> impl Future<Output = HandlerResult<crate::LoadedSourcesResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::LoadedSources::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Evaluates the given expression in the context of a stack frame.
+    /// The expression has access to any variables and arguments that are in scope.
+    fn evaluate(
+        &mut self,
+        arguments: crate::EvaluateArguments,
+    ) -> impl Future<Output = HandlerResult<crate::EvaluateResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Evaluate::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Evaluates the given `value` expression and assigns it to the `expression` which must be a modifiable l-value.
+    /// The expressions have access to any variables and arguments that are in scope of the specified frame.
+    /// Clients should only call this request if the corresponding capability `supportsSetExpression` is true.
+    /// If a debug adapter implements both `setExpression` and `setVariable`, a client uses `setExpression` if the variable has an `evaluateName` property.
+    fn set_expression(
+        &mut self,
+        arguments: crate::SetExpressionArguments,
+    ) -> impl Future<Output = HandlerResult<crate::SetExpressionResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::SetExpression::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// This request retrieves the possible step-in targets for the specified stack frame.
+    /// These targets can be used in the `stepIn` request.
+    /// Clients should only call this request if the corresponding capability `supportsStepInTargetsRequest` is true.
+    fn step_in_targets(
+        &mut self,
+        arguments: crate::StepInTargetsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::StepInTargetsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::StepInTargets::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// This request retrieves the possible goto targets for the specified source location.
+    /// These targets can be used in the `goto` request.
+    /// Clients should only call this request if the corresponding capability `supportsGotoTargetsRequest` is true.
+    fn goto_targets(
+        &mut self,
+        arguments: crate::GotoTargetsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::GotoTargetsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::GotoTargets::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Returns a list of possible completions for a given caret position and text.
+    /// Clients should only call this request if the corresponding capability `supportsCompletionsRequest` is true.
+    fn completions(
+        &mut self,
+        arguments: crate::CompletionsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::CompletionsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Completions::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Retrieves the details of the exception that caused this event to be raised.
+    /// Clients should only call this request if the corresponding capability `supportsExceptionInfoRequest` is true.
+    fn exception_info(
+        &mut self,
+        arguments: crate::ExceptionInfoArguments,
+    ) -> impl Future<Output = HandlerResult<crate::ExceptionInfoResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::ExceptionInfo::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Reads bytes from memory at the provided location.
+    /// Clients should only call this request if the corresponding capability `supportsReadMemoryRequest` is true.
+    fn read_memory(
+        &mut self,
+        arguments: crate::ReadMemoryArguments,
+    ) -> impl Future<Output = HandlerResult<crate::ReadMemoryResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::ReadMemory::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Writes bytes to memory at the provided location.
+    /// Clients should only call this request if the corresponding capability `supportsWriteMemoryRequest` is true.
+    fn write_memory(
+        &mut self,
+        arguments: crate::WriteMemoryArguments,
+    ) -> impl Future<Output = HandlerResult<crate::WriteMemoryResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::WriteMemory::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Disassembles code stored at the provided location.
+    /// Clients should only call this request if the corresponding capability `supportsDisassembleRequest` is true.
+    fn disassemble(
+        &mut self,
+        arguments: crate::DisassembleArguments,
+    ) -> impl Future<Output = HandlerResult<crate::DisassembleResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Disassemble::COMMAND);
+        async move { Err(error) }
+    }
+
+    /// Looks up information about a location reference previously returned by the debug adapter.
+    fn locations(
+        &mut self,
+        arguments: crate::LocationsArguments,
+    ) -> impl Future<Output = HandlerResult<crate::LocationsResponse>> + Send {
+        let _ = arguments;
+        let error = not_implemented::<Self>(requests::Locations::COMMAND);
+        async move { Err(error) }
+    }
+}
+
+/// Handles a request by calling the matching [`DebugAdapter`] method.
+///
+/// Requests that are not defined by the protocol, and requests with invalid arguments, get an error response.
+/// The returned response has `seq` set to 0, it should be assigned when the response is sent.
+pub async fn dispatch<A: DebugAdapter>(adapter: &mut A, request: &Request) -> Response {
+    let typed = match request.typed() {
+        Ok(typed) => typed,
+        Err(e) => return AdapterError::invalid_arguments(e).into_response(request),
+    };
+    match typed {
+        Requests::Cancel(arguments) => {
+            let result = adapter.cancel(arguments).await;
+            respond::<requests::Cancel>(request, result)
+        }
+        Requests::Initialize(arguments) => {
+            let result = adapter.initialize(arguments).await;
+            let result = result.map(capabilities::complete::<A>);
+            respond::<requests::Initialize>(request, result)
+        }
+        Requests::ConfigurationDone(arguments) => {
+            let result = adapter.configuration_done(arguments).await;
+            respond::<requests::ConfigurationDone>(request, result)
+        }
+        Requests::Launch(arguments) => {
+            let result = adapter.launch(arguments).await;
+            respond::<requests::Launch>(request, result)
+        }
+        Requests::Attach(arguments) => {
+            let result = adapter.attach(arguments).await;
+            respond::<requests::Attach>(request, result)
+        }
+        Requests::Restart(arguments) => {
+            let result = adapter.restart(arguments).await;
+            respond::<requests::Restart>(request, result)
+        }
+        Requests::Disconnect(arguments) => {
+            let result = adapter.disconnect(arguments).await;
+            respond::<requests::Disconnect>(request, result)
+        }
+        Requests::Terminate(arguments) => {
+            let result = adapter.terminate(arguments).await;
+            respond::<requests::Terminate>(request, result)
+        }
+        Requests::BreakpointLocations(arguments) => {
+            let result = adapter.breakpoint_locations(arguments).await;
+            respond::<requests::BreakpointLocations>(request, result)
+        }
+        Requests::SetBreakpoints(arguments) => {
+            let result = adapter.set_breakpoints(arguments).await;
+            respond::<requests::SetBreakpoints>(request, result)
+        }
+        Requests::SetFunctionBreakpoints(arguments) => {
+            let result = adapter.set_function_breakpoints(arguments).await;
+            respond::<requests::SetFunctionBreakpoints>(request, result)
+        }
+        Requests::SetExceptionBreakpoints(arguments) => {
+            let result = adapter.set_exception_breakpoints(arguments).await;
+            respond::<requests::SetExceptionBreakpoints>(request, result)
+        }
+        Requests::DataBreakpointInfo(arguments) => {
+            let result = adapter.data_breakpoint_info(arguments).await;
+            respond::<requests::DataBreakpointInfo>(request, result)
+        }
+        Requests::SetDataBreakpoints(arguments) => {
+            let result = adapter.set_data_breakpoints(arguments).await;
+            respond::<requests::SetDataBreakpoints>(request, result)
+        }
+        Requests::SetInstructionBreakpoints(arguments) => {
+            let result = adapter.set_instruction_breakpoints(arguments).await;
+            respond::<requests::SetInstructionBreakpoints>(request, result)
+        }
+        Requests::Continue(arguments) => {
+            let result = adapter.continue_(arguments).await;
+            respond::<requests::Continue>(request, result)
+        }
+        Requests::Next(arguments) => {
+            let result = adapter.next(arguments).await;
+            respond::<requests::Next>(request, result)
+        }
+        Requests::StepIn(arguments) => {
+            let result = adapter.step_in(arguments).await;
+            respond::<requests::StepIn>(request, result)
+        }
+        Requests::StepOut(arguments) => {
+            let result = adapter.step_out(arguments).await;
+            respond::<requests::StepOut>(request, result)
+        }
+        Requests::StepBack(arguments) => {
+            let result = adapter.step_back(arguments).await;
+            respond::<requests::StepBack>(request, result)
+        }
+        Requests::ReverseContinue(arguments) => {
+            let result = adapter.reverse_continue(arguments).await;
+            respond::<requests::ReverseContinue>(request, result)
+        }
+        Requests::RestartFrame(arguments) => {
+            let result = adapter.restart_frame(arguments).await;
+            respond::<requests::RestartFrame>(request, result)
+        }
+        Requests::Goto(arguments) => {
+            let result = adapter.goto(arguments).await;
+            respond::<requests::Goto>(request, result)
+        }
+        Requests::Pause(arguments) => {
+            let result = adapter.pause(arguments).await;
+            respond::<requests::Pause>(request, result)
+        }
+        Requests::StackTrace(arguments) => {
+            let result = adapter.stack_trace(arguments).await;
+            respond::<requests::StackTrace>(request, result)
+        }
+        Requests::Scopes(arguments) => {
+            let result = adapter.scopes(arguments).await;
+            respond::<requests::Scopes>(request, result)
+        }
+        Requests::Variables(arguments) => {
+            let result = adapter.variables(arguments).await;
+            respond::<requests::Variables>(request, result)
+        }
+        Requests::SetVariable(arguments) => {
+            let result = adapter.set_variable(arguments).await;
+            respond::<requests::SetVariable>(request, result)
+        }
+        Requests::Source(arguments) => {
+            let result = adapter.source(arguments).await;
+            respond::<requests::Source>(request, result)
+        }
+        Requests::Threads(arguments) => {
+            let result = adapter.threads(arguments).await;
+            respond::<requests::Threads>(request, result)
+        }
+        Requests::TerminateThreads(arguments) => {
+            let result = adapter.terminate_threads(arguments).await;
+            respond::<requests::TerminateThreads>(request, result)
+        }
+        Requests::Modules(arguments) => {
+            let result = adapter.modules(arguments).await;
+            respond::<requests::Modules>(request, result)
+        }
+        Requests::LoadedSources(arguments) => {
+            let result = adapter.loaded_sources(arguments).await;
+            respond::<requests::LoadedSources>(request, result)
+        }
+        Requests::Evaluate(arguments) => {
+            let result = adapter.evaluate(arguments).await;
+            respond::<requests::Evaluate>(request, result)
+        }
+        Requests::SetExpression(arguments) => {
+            let result = adapter.set_expression(arguments).await;
+            respond::<requests::SetExpression>(request, result)
+        }
+        Requests::StepInTargets(arguments) => {
+            let result = adapter.step_in_targets(arguments).await;
+            respond::<requests::StepInTargets>(request, result)
+        }
+        Requests::GotoTargets(arguments) => {
+            let result = adapter.goto_targets(arguments).await;
+            respond::<requests::GotoTargets>(request, result)
+        }
+        Requests::Completions(arguments) => {
+            let result = adapter.completions(arguments).await;
+            respond::<requests::Completions>(request, result)
+        }
+        Requests::ExceptionInfo(arguments) => {
+            let result = adapter.exception_info(arguments).await;
+            respond::<requests::ExceptionInfo>(request, result)
+        }
+        Requests::ReadMemory(arguments) => {
+            let result = adapter.read_memory(arguments).await;
+            respond::<requests::ReadMemory>(request, result)
+        }
+        Requests::WriteMemory(arguments) => {
+            let result = adapter.write_memory(arguments).await;
+            respond::<requests::WriteMemory>(request, result)
+        }
+        Requests::Disassemble(arguments) => {
+            let result = adapter.disassemble(arguments).await;
+            respond::<requests::Disassemble>(request, result)
+        }
+        Requests::Locations(arguments) => {
+            let result = adapter.locations(arguments).await;
+            respond::<requests::Locations>(request, result)
+        }
+        _ => AdapterError::unsupported(&request.command).into_response(request),
+    }
+}
@@ -0,0 +1,475 @@
+use serde_json::Value;
+
+use crate::adapter::DebugAdapter;
+use crate::messages;
+use crate::requests::{Request, CAPABILITY_GATED_FIELDS, CAPABILITY_GATED_REQUESTS};
+use crate::Capabilities;
+
+/// Builds the capabilities that advertise the given requests.
+///
+/// A capability is set to true when every request it gates is handled, e.g.
+/// `supportsDataBreakpoints` needs both `dataBreakpointInfo` and
+/// `setDataBreakpoints`. Capabilities that don't gate a request are left unset.
+pub fn from_handled_requests(handled: &[&str]) -> Capabilities {
+    let mut capabilities = Capabilities::default();
+    fill_from_handled_requests(&mut capabilities, handled);
+    capabilities
+}
+
+/// Sets capabilities that are not set yet according to the handled requests,
+/// the same way as [`from_handled_requests`].
+pub fn fill_from_handled_requests(capabilities: &mut Capabilities, handled: &[&str]) {
+    for (_, capability) in CAPABILITY_GATED_REQUESTS {
+        if capabilities.flag(capability).is_some() {
+            continue;
+        }
+        let supported = CAPABILITY_GATED_REQUESTS
+            .iter()
+            .filter(|(_, c)| c == capability)
+            .all(|(command, _)| handled.contains(command));
+        if supported {
+            capabilities.set_flag(capability, Some(true));
+        }
+    }
+}
+
+/// Finds requests whose capability disagrees with whether they are handled.
+pub fn mismatches(capabilities: &Capabilities, handled: &[&str]) -> Vec<CapabilityMismatch> {
+    CAPABILITY_GATED_REQUESTS
+        .iter()
+        .filter_map(|&(command, capability)| {
+            let advertised = capabilities.flag(capability) == Some(true);
+            (advertised != handled.contains(&command)).then_some(CapabilityMismatch {
+                command,
+                capability,
+                advertised,
+            })
+        })
+        .collect()
+}
+
+/// Returns the capability that has to be true for clients to send a request
+/// with the given command, like [`Request::REQUIRED_CAPABILITY`].
+pub fn required_capability(command: &str) -> Option<&'static str> {
+    CAPABILITY_GATED_REQUESTS
+        .iter()
+        .find(|(c, _)| *c == command)
+        .map(|&(_, capability)| capability)
+}
+
+/// Returns the capability required by request `R` if the adapter did not advertise it.
+pub fn missing_capability<R: Request>(capabilities: &Capabilities) -> Option<&'static str> {
+    R::REQUIRED_CAPABILITY.filter(|capability| capabilities.flag(capability) != Some(true))
+}
+
+/// Lists argument fields of request `R` that the adapter will ignore, because
+/// it did not advertise the capabilities they depend on.
+pub fn ignored_fields<R: Request>(
+    arguments: &R::Arguments,
+    capabilities: &Capabilities,
+) -> Result<Vec<IgnoredField>, serde_json::Error> {
+    let mut arguments = serde_json::to_value(arguments)?;
+    Ok(visit_ignored_fields(
+        R::COMMAND,
+        &mut arguments,
+        capabilities,
+        false,
+    ))
+}
+
+/// Removes argument fields of request `R` that the adapter will ignore,
+/// returning the fields that were removed.
+pub fn strip_ignored_fields<R: Request>(
+    arguments: &mut R::Arguments,
+    capabilities: &Capabilities,
+) -> Result<Vec<IgnoredField>, serde_json::Error> {
+    let mut value = serde_json::to_value(&*arguments)?;
+    let ignored = visit_ignored_fields(R::COMMAND, &mut value, capabilities, true);
+    if !ignored.is_empty() {
+        *arguments = serde_json::from_value(value)?;
+    }
+    Ok(ignored)
+}
+
+/// Lists argument fields of a request that the adapter will ignore, like
+/// [`ignored_fields`].
+pub fn ignored_request_fields(
+    request: &messages::Request,
+    capabilities: &Capabilities,
+) -> Vec<IgnoredField> {
+    let Some(mut arguments) = request.arguments.clone() else {
+        return Vec::new();
+    };
+    visit_ignored_fields(&request.command, &mut arguments, capabilities, false)
+}
+
+/// Removes argument fields of a request that the adapter will ignore, like
+/// [`strip_ignored_fields`].
+pub fn strip_ignored_request_fields(
+    request: &mut messages::Request,
+    capabilities: &Capabilities,
+) -> Vec<IgnoredField> {
+    let Some(arguments) = &mut request.arguments else {
+        return Vec::new();
+    };
+    visit_ignored_fields(&request.command, arguments, capabilities, true)
+}
+
+fn visit_ignored_fields(
+    command: &str,
+    arguments: &mut Value,
+    capabilities: &Capabilities,
+    strip: bool,
+) -> Vec<IgnoredField> {
+    let mut ignored = Vec::new();
+    for &(_, path, capability) in CAPABILITY_GATED_FIELDS
+        .iter()
+        .filter(|(c, _, _)| *c == command)
+    {
+        if capabilities.flag(capability) == Some(true) {
+            continue;
+        }
+        let path = path.split('.').collect::<Vec<_>>();
+        visit_path(arguments, &path, "", strip, &mut |path| {
+            ignored.push(IgnoredField { path, capability })
+        });
+    }
+    ignored
+}
+
+/// Calls `found` with the location of every field at `path` that is set,
+/// removing it if `strip` is true. Arrays along the way are searched element
+/// by element.
+fn visit_path(
+    value: &mut Value,
+    path: &[&str],
+    location: &str,
+    strip: bool,
+    found: &mut dyn FnMut(String),
+) {
+    match value {
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                visit_path(item, path, &format!("{location}[{i}]"), strip, found);
+            }
+        }
+        Value::Object(fields) => {
+            let [field, rest @ ..] = path else {
+                return;
+            };
+            let location = if location.is_empty() {
+                field.to_string()
+            } else {
+                format!("{location}.{field}")
+            };
+            if !rest.is_empty() {
+                if let Some(value) = fields.get_mut(*field) {
+                    visit_path(value, rest, &location, strip, found);
+                }
+            } else if fields.get(*field).is_some_and(|v| !v.is_null()) {
+                found(location);
+                if strip {
+                    fields.remove(*field);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Completes the capabilities returned by an adapter's `initialize` handler
+/// using [`DebugAdapter::HANDLED_REQUESTS`], logging any disagreement.
+pub(crate) fn complete<A: DebugAdapter>(mut capabilities: Capabilities) -> Capabilities {
+    if A::HANDLED_REQUESTS.is_empty() {
+        return capabilities;
+    }
+    fill_from_handled_requests(&mut capabilities, A::HANDLED_REQUESTS);
+    for mismatch in mismatches(&capabilities, A::HANDLED_REQUESTS) {
+        log::warn!("{mismatch}");
+    }
+    capabilities
+}
+
+/// A request that is handled but not advertised, or the other way round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilityMismatch {
+    pub command: &'static str,
+    pub capability: &'static str,
+    /// Whether the capability is advertised, in which case the request is not handled.
+    pub advertised: bool,
+}
+
+impl std::fmt::Display for CapabilityMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let CapabilityMismatch {
+            command,
+            capability,
+            advertised,
+        } = self;
+        if *advertised {
+            write!(
+                f,
+                "capability `{capability}` is advertised, but `{command}` requests are not handled"
+            )
+        } else {
+            write!(
+                f,
+                "`{command}` requests are handled, but capability `{capability}` is not advertised"
+            )
+        }
+    }
+}
+
+/// An argument field that is set, but depends on a capability that the adapter
+/// did not advertise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoredField {
+    /// Location of the field in the arguments, e.g. `breakpoints[1].logMessage`.
+    pub path: String,
+    pub capability: &'static str,
+}
+
+impl std::fmt::Display for IgnoredField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let IgnoredField { path, capability } = self;
+        write!(
+            f,
+            "`{path}` is ignored, capability `{capability}` is not advertised"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::{dispatch, HandlerResult};
+    use crate::requests;
+
+    #[test]
+    fn derives_capabilities_from_handled_requests() {
+        let capabilities = from_handled_requests(&["stepBack", "reverseContinue", "goto"]);
+        assert_eq!(capabilities.supports_step_back, Some(true));
+        // `goto` is only useful together with `gotoTargets`.
+        assert_eq!(capabilities.supports_goto_targets_request, None);
+        assert_eq!(capabilities.supports_cancel_request, None);
+        assert_eq!(
+            mismatches(&capabilities, &["stepBack", "reverseContinue", "goto"]),
+            [CapabilityMismatch {
+                command: "goto",
+                capability: "supportsGotoTargetsRequest",
+                advertised: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_advertised_requests_that_are_not_handled() {
+        let capabilities = Capabilities {
+            supports_set_expression: Some(true),
+            supports_cancel_request: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(
+            mismatches(&capabilities, &["cancel"]),
+            [
+                CapabilityMismatch {
+                    command: "cancel",
+                    capability: "supportsCancelRequest",
+                    advertised: false,
+                },
+                CapabilityMismatch {
+                    command: "setExpression",
+                    capability: "supportsSetExpression",
+                    advertised: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_missing_capabilities() {
+        assert_eq!(
+            required_capability("setExpression"),
+            Some("supportsSetExpression")
+        );
+        assert_eq!(required_capability("threads"), None);
+
+        let capabilities = Capabilities {
+            supports_step_back: Some(true),
+            supports_set_expression: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(
+            missing_capability::<requests::StepBack>(&capabilities),
+            None
+        );
+        assert_eq!(missing_capability::<requests::Threads>(&capabilities), None);
+        assert_eq!(
+            missing_capability::<requests::SetExpression>(&capabilities),
+            Some("supportsSetExpression")
+        );
+        assert_eq!(
+            missing_capability::<requests::Cancel>(&capabilities),
+            Some("supportsCancelRequest")
+        );
+    }
+
+    #[test]
+    fn finds_ignored_fields() {
+        let mut arguments: crate::SetBreakpointsArguments =
+            serde_json::from_value(serde_json::json!({
+                "source": { "path": "main.rs" },
+                "breakpoints": [
+                    { "line": 1, "condition": "x > 1" },
+                    { "line": 2, "hitCondition": "3", "logMessage": "x = {x}" },
+                ],
+            }))
+            .unwrap();
+        let capabilities = Capabilities {
+            supports_conditional_breakpoints: Some(true),
+            supports_log_points: Some(false),
+            ..Default::default()
+        };
+        let ignored = ignored_fields::<requests::SetBreakpoints>(&arguments, &capabilities);
+        let expected = [
+            IgnoredField {
+                path: "breakpoints[1].hitCondition".to_owned(),
+                capability: "supportsHitConditionalBreakpoints",
+            },
+            IgnoredField {
+                path: "breakpoints[1].logMessage".to_owned(),
+                capability: "supportsLogPoints",
+            },
+        ];
+        assert_eq!(ignored.unwrap(), expected);
+
+        let stripped =
+            strip_ignored_fields::<requests::SetBreakpoints>(&mut arguments, &capabilities);
+        assert_eq!(stripped.unwrap(), expected);
+        let breakpoints = arguments.breakpoints.unwrap();
+        assert_eq!(breakpoints[0].condition.as_deref(), Some("x > 1"));
+        assert_eq!(breakpoints[1].hit_condition, None);
+        assert_eq!(breakpoints[1].log_message, None);
+    }
+
+    #[test]
+    fn strips_ignored_request_fields() {
+        let mut request = crate::messages::Request {
+            seq: 1,
+            command: "disconnect".to_owned(),
+            arguments: Some(serde_json::json!({ "restart": false, "suspendDebuggee": true })),
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        };
+        let capabilities = Capabilities::default();
+        let ignored = strip_ignored_request_fields(&mut request, &capabilities);
+        assert_eq!(
+            ignored,
+            [IgnoredField {
+                path: "suspendDebuggee".to_owned(),
+                capability: "supportSuspendDebuggee",
+            }]
+        );
+        assert_eq!(
+            request.arguments,
+            Some(serde_json::json!({ "restart": false }))
+        );
+        assert!(ignored_request_fields(&request, &capabilities).is_empty());
+    }
+
+    #[test]
+    fn value_format_fields_are_gated() {
+        for command in ["setVariable", "setExpression"] {
+            let mut request = crate::messages::Request {
+                seq: 1,
+                command: command.to_owned(),
+                arguments: Some(serde_json::json!({
+                    "variablesReference": 1,
+                    "name": "x",
+                    "expression": "x",
+                    "value": "1",
+                    "format": { "hex": true },
+                })),
+                #[cfg(feature = "extra-fields")]
+                extra: Default::default(),
+            };
+            let ignored = strip_ignored_request_fields(&mut request, &Capabilities::default());
+            assert_eq!(
+                ignored,
+                [IgnoredField {
+                    path: "format".to_owned(),
+                    capability: "supportsValueFormattingOptions",
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn stack_trace_paging_fields_are_gated() {
+        let arguments: crate::StackTraceArguments = serde_json::from_value(serde_json::json!({
+            "threadId": 1,
+            "startFrame": 20,
+            "levels": 20,
+        }))
+        .unwrap();
+        let capability = "supportsDelayedStackTraceLoading";
+        assert_eq!(
+            ignored_fields::<requests::StackTrace>(&arguments, &Capabilities::default()).unwrap(),
+            [
+                IgnoredField {
+                    path: "startFrame".to_owned(),
+                    capability
+                },
+                IgnoredField {
+                    path: "levels".to_owned(),
+                    capability
+                },
+            ]
+        );
+        let capabilities = Capabilities {
+            supports_delayed_stack_trace_loading: Some(true),
+            ..Default::default()
+        };
+        assert!(
+            ignored_fields::<requests::StackTrace>(&arguments, &capabilities)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    struct StepBackAdapter;
+
+    impl DebugAdapter for StepBackAdapter {
+        const HANDLED_REQUESTS: &'static [&'static str] = &[
+            requests::Initialize::COMMAND,
+            requests::StepBack::COMMAND,
+            requests::ReverseContinue::COMMAND,
+        ];
+
+        async fn initialize(
+            &mut self,
+            _: crate::InitializeRequestArguments,
+        ) -> HandlerResult<Capabilities> {
+            Ok(Capabilities {
+                supports_cancel_request: Some(false),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn initialize_response_is_completed() {
+        let request = crate::messages::Request {
+            seq: 1,
+            command: "initialize".to_owned(),
+            arguments: Some(serde_json::json!({ "adapterID": "test" })),
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        };
+        let response = dispatch(&mut StepBackAdapter, &request).await;
+        let capabilities = response.into_typed::<requests::Initialize>().unwrap();
+        assert_eq!(capabilities.supports_step_back, Some(true));
+        assert_eq!(capabilities.supports_cancel_request, Some(false));
+        assert_eq!(capabilities.supports_set_expression, None);
+    }
+}
@@ -0,0 +1,615 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+use crate::adapter::{self, AdapterError, HandlerResult};
+use crate::capabilities;
+use crate::codec::MessageCodec;
+use crate::correlator::{CorrelationError, Correlator};
+use crate::framing::FramingError;
+use crate::messages::{Events, Message, Request, Requests, Response, ResponseError};
+use crate::requests::{self, Request as _};
+use crate::Capabilities;
+
+type Waiter = oneshot::Sender<Result<Response, DapError>>;
+
+/// Number of messages buffered in each direction. Once the buffer is full,
+/// sending waits for the transport, and reading waits for events to be
+/// received.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Client side of a debug adapter connection.
+///
+/// Requests are sent with [`Client::send`], which resolves once the adapter
+/// responds. Events sent by the adapter are delivered to the receiver returned
+/// from [`Client::new`]; responses are not read while that receiver is full,
+/// so it should be drained or dropped. Reverse requests sent by the adapter are
+/// answered by a [`ReverseRequestHandler`]. The client can be cloned to send
+/// requests concurrently.
+///
+/// Once the adapter has responded to `initialize`, requests that need a
+/// capability the adapter did not advertise are handled according to
+/// [`Client::set_capability_check`].
+#[derive(Clone)]
+pub struct Client {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    state: Mutex<State>,
+    outgoing: mpsc::Sender<Message>,
+}
+
+struct State {
+    correlator: Correlator<Waiter>,
+    /// Set once the adapter stops sending messages, after which no response can arrive.
+    closed: bool,
+    /// Capabilities from the `initialize` response, updated by `capabilities` events.
+    capabilities: Option<Capabilities>,
+    capability_check: CapabilityCheck,
+}
+
+/// What [`Client::send`] does with a request that needs a capability the
+/// adapter did not advertise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CapabilityCheck {
+    /// Fail with [`DapError::Unsupported`] without sending the request.
+    #[default]
+    Refuse,
+    /// Log a warning and send the request anyway.
+    Warn,
+    /// Send the request without checking.
+    Ignore,
+}
+
+impl Client {
+    /// Starts a client over the given transport, rejecting reverse requests.
+    ///
+    /// Reading and writing happen on background tasks, so this must be called
+    /// from within a tokio runtime.
+    pub fn new<R, W>(reader: R, writer: W) -> (Client, mpsc::Receiver<Events>)
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        Client::with_handler(reader, writer, ())
+    }
+
+    /// Starts a client over the given transport, answering reverse requests
+    /// with `handler`.
+    pub fn with_handler<R, W, H>(
+        reader: R,
+        writer: W,
+        handler: H,
+    ) -> (Client, mpsc::Receiver<Events>)
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+        W: AsyncWrite + Unpin + Send + 'static,
+        H: ReverseRequestHandler,
+    {
+        let (outgoing, outgoing_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let (events, events_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let client = Client {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State {
+                    correlator: Correlator::new(),
+                    closed: false,
+                    capabilities: None,
+                    capability_check: CapabilityCheck::default(),
+                }),
+                outgoing,
+            }),
+        };
+        tokio::spawn(write_loop(
+            FramedWrite::new(writer, MessageCodec::new()),
+            outgoing_rx,
+        ));
+        tokio::spawn(read_loop(
+            FramedRead::new(reader, MessageCodec::new()),
+            client.inner.clone(),
+            events,
+            Arc::new(handler),
+        ));
+        (client, events_rx)
+    }
+
+    /// Sends request `R` and waits for its response.
+    pub async fn send<R: requests::Request>(
+        &self,
+        arguments: R::Arguments,
+    ) -> Result<R::Response, DapError> {
+        let (waiter, response) = oneshot::channel();
+        let permit = self
+            .inner
+            .outgoing
+            .reserve()
+            .await
+            .map_err(|_| DapError::Closed)?;
+        {
+            // The sequence number is allocated right before sending, so
+            // requests are written in the order of their sequence numbers.
+            let mut state = self.inner.state.lock().unwrap();
+            if state.closed {
+                return Err(DapError::Closed);
+            }
+            state.check_capability::<R>()?;
+            let request = state
+                .correlator
+                .request::<R>(arguments, waiter)
+                .map_err(DapError::Encode)?;
+            permit.send(Message::Request(request));
+        }
+        let response = response.await.map_err(|_| DapError::Closed)??;
+        Ok(response.into_typed::<R>()?)
+    }
+
+    /// Capabilities advertised by the adapter, once it has responded to `initialize`.
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.inner.state.lock().unwrap().capabilities.clone()
+    }
+
+    /// Sets how requests the adapter did not advertise are handled.
+    pub fn set_capability_check(&self, check: CapabilityCheck) {
+        self.inner.state.lock().unwrap().capability_check = check;
+    }
+}
+
+impl State {
+    fn check_capability<R: requests::Request>(&self) -> Result<(), DapError> {
+        let Some(capabilities) = &self.capabilities else {
+            return Ok(());
+        };
+        let Some(capability) = capabilities::missing_capability::<R>(capabilities) else {
+            return Ok(());
+        };
+        match self.capability_check {
+            CapabilityCheck::Refuse => Err(DapError::Unsupported {
+                command: R::COMMAND,
+                capability,
+            }),
+            CapabilityCheck::Warn => {
+                log::warn!(
+                    "sending `{}` request, but the adapter did not advertise `{capability}`",
+                    R::COMMAND
+                );
+                Ok(())
+            }
+            CapabilityCheck::Ignore => Ok(()),
+        }
+    }
+
+    fn update_capabilities(&mut self, capabilities: Capabilities) {
+        self.capabilities = Some(match &self.capabilities {
+            Some(current) => current.merge(capabilities),
+            None => capabilities,
+        });
+    }
+}
+
+async fn write_loop<W>(
+    mut sink: FramedWrite<W, MessageCodec>,
+    mut outgoing: mpsc::Receiver<Message>,
+) where
+    W: AsyncWrite + Unpin,
+{
+    while let Some(message) = outgoing.recv().await {
+        if sink.send(message).await.is_err() {
+            break;
+        }
+    }
+}
+
+async fn read_loop<R, H>(
+    mut stream: FramedRead<R, MessageCodec>,
+    inner: Arc<Inner>,
+    events: mpsc::Sender<Events>,
+    handler: Arc<H>,
+) where
+    R: AsyncRead + Unpin,
+    H: ReverseRequestHandler,
+{
+    while let Some(message) = stream.next().await {
+        match message {
+            Ok(Message::Response(response)) => inner.handle_response(response),
+            Ok(Message::Event(event)) => {
+                match &event.event {
+                    Events::Initialized(Some(capabilities)) => {
+                        inner.update_capabilities(capabilities.clone())
+                    }
+                    Events::Capabilities(body) => {
+                        inner.update_capabilities(body.capabilities.clone())
+                    }
+                    _ => {}
+                }
+                // The event receiver being dropped only means nobody is
+                // interested in events; requests still work.
+                let _ = events.send(event.event).await;
+            }
+            // Reverse requests are answered on their own tasks, so handlers
+            // that take a while don't hold up responses.
+            Ok(Message::Request(request)) => {
+                tokio::spawn(handle_reverse_request(
+                    inner.clone(),
+                    handler.clone(),
+                    request,
+                ));
+            }
+            // A message that fails to decode is skipped, the frame itself has
+            // already been consumed.
+            Err(FramingError::Json(_)) => {}
+            Err(_) => break,
+        }
+    }
+    let mut state = inner.state.lock().unwrap();
+    state.closed = true;
+    for (_, waiter) in state.correlator.drain() {
+        let _ = waiter.send(Err(DapError::Closed));
+    }
+}
+
+impl Inner {
+    fn handle_response(&self, response: Response) {
+        let state = &mut *self.state.lock().unwrap();
+        match state.correlator.resolve(&response) {
+            Ok(waiter) => {
+                if response.command == requests::Initialize::COMMAND && response.success {
+                    if let Ok(capabilities) = response.clone().into_typed::<requests::Initialize>()
+                    {
+                        state.update_capabilities(capabilities);
+                    }
+                }
+                let _ = waiter.send(Ok(response));
+            }
+            Err(e @ CorrelationError::CommandMismatch { .. }) => {
+                if let Some(waiter) = state.correlator.take(response.request_seq) {
+                    let _ = waiter.send(Err(DapError::Protocol(e)));
+                }
+            }
+            // Responses that don't belong to any request have nobody to be
+            // reported to.
+            Err(_) => {}
+        }
+    }
+
+    fn update_capabilities(&self, capabilities: Capabilities) {
+        self.state.lock().unwrap().update_capabilities(capabilities);
+    }
+
+    async fn respond(&self, mut response: Response) {
+        let Ok(permit) = self.outgoing.reserve().await else {
+            return;
+        };
+        response.seq = self.state.lock().unwrap().correlator.next_seq();
+        permit.send(Message::Response(response));
+    }
+}
+
+/// Handler for reverse requests, which the adapter sends to the client.
+///
+/// Every method has a default implementation that responds with an
+/// "unsupported request" error, so a client only implements the requests it
+/// supports. `()` rejects all reverse requests.
+pub trait ReverseRequestHandler: Send + Sync + 'static {
+    /// Runs a command in a terminal, see [`requests::RunInTerminal`].
+    fn run_in_terminal(
+        &self,
+        arguments: crate::RunInTerminalRequestArguments,
+    ) -> impl Future<Output = HandlerResult<crate::RunInTerminalResponse>> + Send {
+        let _ = arguments;
+        async { Err(AdapterError::unsupported(requests::RunInTerminal::COMMAND)) }
+    }
+
+    /// Starts a child debug session, see [`requests::StartDebugging`].
+    fn start_debugging(
+        &self,
+        arguments: crate::StartDebuggingRequestArguments,
+    ) -> impl Future<Output = HandlerResult<()>> + Send {
+        let _ = arguments;
+        async { Err(AdapterError::unsupported(requests::StartDebugging::COMMAND)) }
+    }
+}
+
+impl ReverseRequestHandler for () {}
+
+async fn handle_reverse_request<H: ReverseRequestHandler>(
+    inner: Arc<Inner>,
+    handler: Arc<H>,
+    request: Request,
+) {
+    let response = match request.typed() {
+        Ok(Requests::RunInTerminal(arguments)) => {
+            let result = handler.run_in_terminal(arguments).await;
+            adapter::respond::<requests::RunInTerminal>(&request, result)
+        }
+        Ok(Requests::StartDebugging(arguments)) => {
+            let result = handler.start_debugging(arguments).await;
+            adapter::respond::<requests::StartDebugging>(&request, result)
+        }
+        Ok(_) => AdapterError::unsupported(&request.command).into_response(&request),
+        Err(e) => AdapterError::invalid_arguments(e).into_response(&request),
+    };
+    inner.respond(response).await;
+}
+
+/// Errors returned by [`Client::send`].
+#[derive(Debug)]
+pub enum DapError {
+    /// Request arguments could not be encoded.
+    Encode(serde_json::Error),
+    /// Adapter responded with an error, or with a body that does not match the request.
+    Response(ResponseError),
+    /// Adapter responded with a response that does not match the request.
+    Protocol(CorrelationError),
+    /// Request needs a capability that the adapter did not advertise.
+    Unsupported {
+        command: &'static str,
+        capability: &'static str,
+    },
+    /// Connection was closed before a response was received.
+    Closed,
+}
+
+impl std::fmt::Display for DapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DapError::Encode(e) => write!(f, "failed to encode request: {e}"),
+            DapError::Response(e) => write!(f, "{e}"),
+            DapError::Protocol(e) => write!(f, "protocol error: {e}"),
+            DapError::Unsupported {
+                command,
+                capability,
+            } => write!(
+                f,
+                "adapter does not support `{command}` requests (capability `{capability}` is not advertised)"
+            ),
+            DapError::Closed => write!(f, "connection closed"),
+        }
+    }
+}
+
+impl std::error::Error for DapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DapError::Encode(e) => Some(e),
+            DapError::Response(e) => Some(e),
+            DapError::Protocol(e) => Some(e),
+            DapError::Unsupported { .. } | DapError::Closed => None,
+        }
+    }
+}
+
+impl From<ResponseError> for DapError {
+    fn from(e: ResponseError) -> Self {
+        DapError::Response(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        events,
+        messages::{Event, Requests},
+    };
+
+    /// Answers `initialize` and `threads` requests, and emits a `stopped` event after `pause`.
+    async fn fake_adapter(stream: tokio::io::DuplexStream) {
+        let (reader, writer) = tokio::io::split(stream);
+        let mut reader = FramedRead::new(reader, MessageCodec::new());
+        let mut writer = FramedWrite::new(writer, MessageCodec::new());
+        let mut seq = 0;
+        while let Some(Ok(Message::Request(request))) = reader.next().await {
+            seq += 1;
+            let response = match request.typed().unwrap() {
+                Requests::Initialize(_) => {
+                    let capabilities = crate::Capabilities {
+                        supports_step_back: Some(true),
+                        ..Default::default()
+                    };
+                    Response::success::<requests::Initialize>(seq, request.seq, capabilities)
+                        .unwrap()
+                }
+                Requests::Threads(_) => {
+                    let body = crate::ThreadsResponse {
+                        threads: vec![crate::Thread {
+                            id: 1,
+                            name: "main".to_owned(),
+                            #[cfg(feature = "extra-fields")]
+                            extra: Default::default(),
+                        }],
+                        #[cfg(feature = "extra-fields")]
+                        extra: Default::default(),
+                    };
+                    Response::success::<requests::Threads>(seq, request.seq, body).unwrap()
+                }
+                Requests::Pause(args) => {
+                    let stopped = Events::new::<events::Stopped>(crate::StoppedEvent {
+                        reason: crate::StoppedEventReason::Pause,
+                        description: None,
+                        thread_id: Some(args.thread_id),
+                        preserve_focus_hint: None,
+                        text: None,
+                        all_threads_stopped: None,
+                        hit_breakpoint_ids: None,
+                        #[cfg(feature = "extra-fields")]
+                        extra: Default::default(),
+                    });
+                    writer
+                        .send(Message::Event(Box::new(Event::new(seq, stopped))))
+                        .await
+                        .unwrap();
+                    Response::success::<requests::Pause>(seq, request.seq, ()).unwrap()
+                }
+                other => Response::error(seq, request.seq, other.command(), "unsupported", None),
+            };
+            writer.send(Message::Response(response)).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn typed_requests_and_events() {
+        let (client_stream, adapter_stream) = tokio::io::duplex(64);
+        tokio::spawn(fake_adapter(adapter_stream));
+        let (reader, writer) = tokio::io::split(client_stream);
+        let (client, mut events) = Client::new(reader, writer);
+
+        let threads = client
+            .send::<requests::Threads>(crate::ThreadsArgument::default())
+            .await
+            .unwrap();
+        assert_eq!(threads.threads[0].name, "main");
+
+        client
+            .send::<requests::Pause>(crate::PauseArguments {
+                thread_id: 1,
+                #[cfg(feature = "extra-fields")]
+                extra: Default::default(),
+            })
+            .await
+            .unwrap();
+        let Some(Events::Stopped(stopped)) = events.recv().await else {
+            panic!("Expected stopped event");
+        };
+        assert_eq!(stopped.thread_id, Some(1));
+
+        let error = client
+            .send::<requests::Scopes>(crate::ScopesArguments {
+                frame_id: 1,
+                #[cfg(feature = "extra-fields")]
+                extra: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            DapError::Response(ResponseError::Failed { message: Some(m), .. }) if m == "unsupported"
+        ));
+    }
+
+    #[tokio::test]
+    async fn unadvertised_requests_are_refused() {
+        let (client_stream, adapter_stream) = tokio::io::duplex(64);
+        tokio::spawn(fake_adapter(adapter_stream));
+        let (reader, writer) = tokio::io::split(client_stream);
+        let (client, _events) = Client::new(reader, writer);
+        let set_expression = crate::SetExpressionArguments {
+            expression: "x".to_owned(),
+            value: "1".to_owned(),
+            frame_id: None,
+            format: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        };
+
+        // Nothing is known about the adapter before `initialize`.
+        let error = client
+            .send::<requests::SetExpression>(set_expression.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DapError::Response(_)));
+
+        let arguments = serde_json::from_value(serde_json::json!({ "adapterID": "test" }));
+        client
+            .send::<requests::Initialize>(arguments.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            client.capabilities().unwrap().supports_step_back,
+            Some(true)
+        );
+        let error = client
+            .send::<requests::SetExpression>(set_expression.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            DapError::Unsupported {
+                command: "setExpression",
+                capability: "supportsSetExpression",
+            }
+        ));
+
+        client.set_capability_check(CapabilityCheck::Warn);
+        let error = client
+            .send::<requests::SetExpression>(set_expression)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DapError::Response(_)));
+    }
+
+    struct Terminal;
+
+    impl ReverseRequestHandler for Terminal {
+        async fn run_in_terminal(
+            &self,
+            arguments: crate::RunInTerminalRequestArguments,
+        ) -> HandlerResult<crate::RunInTerminalResponse> {
+            assert_eq!(arguments.args, ["ls"]);
+            Ok(crate::RunInTerminalResponse {
+                process_id: Some(42),
+                shell_process_id: None,
+                #[cfg(feature = "extra-fields")]
+                extra: Default::default(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn reverse_requests_are_handled() {
+        let (client_stream, adapter_stream) = tokio::io::duplex(64);
+        let (reader, writer) = tokio::io::split(client_stream);
+        let (_client, _events) = Client::with_handler(reader, writer, Terminal);
+        let (reader, writer) = tokio::io::split(adapter_stream);
+        let mut reader = FramedRead::new(reader, MessageCodec::new());
+        let mut writer = FramedWrite::new(writer, MessageCodec::new());
+
+        let run_in_terminal = serde_json::json!({ "cwd": "/", "args": ["ls"] });
+        let start_debugging = serde_json::json!({ "configuration": {}, "request": "launch" });
+        for (seq, command, arguments) in [
+            (1, "runInTerminal", run_in_terminal),
+            (2, "startDebugging", start_debugging),
+        ] {
+            let request = Request {
+                seq,
+                command: command.to_owned(),
+                arguments: Some(arguments),
+                #[cfg(feature = "extra-fields")]
+                extra: Default::default(),
+            };
+            writer.send(Message::Request(request)).await.unwrap();
+        }
+
+        let Some(Ok(Message::Response(response))) = reader.next().await else {
+            panic!("Expected response");
+        };
+        assert_eq!((response.seq, response.request_seq), (1, 1));
+        let body = response.into_typed::<requests::RunInTerminal>().unwrap();
+        assert_eq!(body.process_id, Some(42));
+        let Some(Ok(Message::Response(response))) = reader.next().await else {
+            panic!("Expected response");
+        };
+        assert_eq!((response.seq, response.request_seq), (2, 2));
+        assert_eq!(
+            response.message.as_deref(),
+            Some("unsupported request: startDebugging")
+        );
+    }
+
+    #[tokio::test]
+    async fn pending_requests_fail_when_closed() {
+        let (client_stream, adapter_stream) = tokio::io::duplex(64);
+        let (reader, writer) = tokio::io::split(client_stream);
+        let (client, _events) = Client::new(reader, writer);
+        let request = tokio::spawn(async move {
+            client
+                .send::<requests::Threads>(crate::ThreadsArgument::default())
+                .await
+        });
+        drop(adapter_stream);
+        assert!(matches!(request.await.unwrap(), Err(DapError::Closed)));
+    }
+}
@@ -0,0 +1,374 @@
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::framing::{
+    encode_message, parse_header_line, set_content_length, FramingError,
+    DEFAULT_MAX_CONTENT_LENGTH, MAX_HEADER_LENGTH,
+};
+use crate::messages::Message;
+
+/// [`Decoder`] and [`Encoder`] for `Content-Length` framed protocol messages.
+///
+/// Use with [`tokio_util::codec::Framed`] (or `FramedRead`/`FramedWrite`) to get a
+/// `Stream` and `Sink` of [`Message`]s. A frame that has only partially arrived is
+/// kept in the read buffer between polls, and the parsed header is remembered so
+/// it is not parsed again when more content arrives.
+///
+/// An invalid header is consumed, and if it still has a `Content-Length`, so is
+/// the content of that message, so that decoding can continue with the next one.
+/// A header with no end within [`MAX_HEADER_LENGTH`] bytes leaves no way to find
+/// the next message, so it is terminal: every later call to `decode` discards
+/// its input and returns [`FramingError::HeaderTooLarge`].
+#[derive(Debug, Clone)]
+pub struct MessageCodec {
+    max_content_length: usize,
+    content_length: Option<usize>,
+    /// Length of the read buffer that is known not to contain the end of the
+    /// header.
+    scanned: usize,
+    /// Remaining content of an invalid message, to be discarded.
+    skip: usize,
+    /// Whether a header without an end was found.
+    failed: bool,
+}
+
+impl MessageCodec {
+    pub fn new() -> Self {
+        MessageCodec {
+            max_content_length: DEFAULT_MAX_CONTENT_LENGTH,
+            content_length: None,
+            scanned: 0,
+            skip: 0,
+            failed: false,
+        }
+    }
+
+    /// Sets the largest `Content-Length` that will be accepted.
+    pub fn with_max_content_length(mut self, max_content_length: usize) -> Self {
+        self.max_content_length = max_content_length;
+        self
+    }
+
+    fn decode_header(&mut self, src: &mut BytesMut) -> Result<Option<usize>, FramingError> {
+        if self.failed {
+            src.clear();
+            return Err(FramingError::HeaderTooLarge);
+        }
+        // Separators can span the end of the previously scanned part.
+        let start = self.scanned.saturating_sub(3);
+        // Headers that end with `\n\n` are invalid, but their end is still known.
+        let end = [&b"\r\n\r\n"[..], b"\n\n"]
+            .into_iter()
+            .filter_map(|separator| {
+                find(&src[start..], separator).map(|end| (start + end, separator.len()))
+            })
+            .min();
+        let Some((end, separator)) = end else {
+            if src.len() > MAX_HEADER_LENGTH {
+                self.failed = true;
+                src.clear();
+                return Err(FramingError::HeaderTooLarge);
+            }
+            self.scanned = src.len();
+            return Ok(None);
+        };
+        self.scanned = 0;
+        // The header is parsed up to the end of its last line.
+        let (content_length, mut result) =
+            parse_header(&src[..end + separator / 2], self.max_content_length);
+        if end + separator > MAX_HEADER_LENGTH {
+            result = Err(FramingError::HeaderTooLarge);
+        } else if separator == 2 && result.is_ok() {
+            // The last line ends with `\r\n`, but the empty line doesn't.
+            let header = String::from_utf8_lossy(&src[..end + 1]).into_owned();
+            result = Err(FramingError::MalformedHeader(header));
+        }
+        // The header is consumed even if it is invalid, so that decoding does not
+        // get stuck on it, and so is the content if its length is known.
+        src.advance(end + separator);
+        if result.is_err() {
+            self.skip = content_length.unwrap_or(0);
+        }
+        result.map(Some)
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Parses the header part of a message, returning the first `Content-Length`
+/// even if the header is invalid.
+fn parse_header(
+    header: &[u8],
+    max_content_length: usize,
+) -> (Option<usize>, Result<usize, FramingError>) {
+    let mut content_length = None;
+    let mut error = None;
+    for line in header.split_inclusive(|&b| b == b'\n') {
+        let line = match line.strip_suffix(b"\r\n") {
+            Some(line) => line,
+            // Lines ending with `\n` are invalid, but can still give the length
+            // of the content to skip.
+            None => {
+                error.get_or_insert(FramingError::MalformedHeader(
+                    String::from_utf8_lossy(line).into_owned(),
+                ));
+                line.strip_suffix(b"\n").unwrap_or(line)
+            }
+        };
+        match parse_header_line(line, usize::MAX) {
+            Ok(Some(length)) => {
+                if let Err(e) = set_content_length(&mut content_length, length) {
+                    error.get_or_insert(e);
+                }
+                if length > max_content_length {
+                    error.get_or_insert(FramingError::ContentTooLarge {
+                        length,
+                        max: max_content_length,
+                    });
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                error.get_or_insert(e);
+            }
+        }
+    }
+    let result = match error {
+        Some(e) => Err(e),
+        None => content_length.ok_or(FramingError::MissingContentLength),
+    };
+    (content_length, result)
+}
+
+impl Default for MessageCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for MessageCodec {
+    type Item = Message;
+    type Error = FramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Message>, FramingError> {
+        if self.skip > 0 {
+            let skipped = self.skip.min(src.len());
+            src.advance(skipped);
+            self.skip -= skipped;
+            if self.skip > 0 {
+                return Ok(None);
+            }
+        }
+        let length = match self.content_length {
+            Some(length) => length,
+            None => match self.decode_header(src)? {
+                Some(length) => {
+                    self.content_length = Some(length);
+                    length
+                }
+                None => return Ok(None),
+            },
+        };
+        if src.len() < length {
+            src.reserve(length - src.len());
+            return Ok(None);
+        }
+        self.content_length = None;
+        let content = src.split_to(length);
+        Ok(Some(serde_json::from_slice(&content)?))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Message>, FramingError> {
+        match self.decode(src)? {
+            Some(message) => Ok(Some(message)),
+            None if src.is_empty() && self.content_length.is_none() && self.skip == 0 => Ok(None),
+            None => Err(FramingError::UnexpectedEof),
+        }
+    }
+}
+
+impl Encoder<Message> for MessageCodec {
+    type Error = FramingError;
+
+    fn encode(&mut self, message: Message, dst: &mut BytesMut) -> Result<(), FramingError> {
+        self.encode(&message, dst)
+    }
+}
+
+impl Encoder<&Message> for MessageCodec {
+    type Error = FramingError;
+
+    fn encode(&mut self, message: &Message, dst: &mut BytesMut) -> Result<(), FramingError> {
+        dst.extend_from_slice(&encode_message(message)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{Event, Events, Request};
+    use futures::{SinkExt, StreamExt};
+    use serde_json::json;
+    use tokio::io::AsyncWriteExt;
+    use tokio_util::codec::{FramedRead, FramedWrite};
+
+    fn request(seq: u64) -> Message {
+        Message::Request(Request {
+            seq,
+            command: "pause".to_owned(),
+            arguments: Some(json!({ "threadId": 1 })),
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+    }
+
+    #[test]
+    fn partial_frames_are_kept_between_calls() {
+        let mut frame = BytesMut::new();
+        MessageCodec::new().encode(&request(1), &mut frame).unwrap();
+        MessageCodec::new().encode(&request(2), &mut frame).unwrap();
+
+        let mut codec = MessageCodec::new();
+        let mut buf = BytesMut::new();
+        let mut decoded = Vec::new();
+        for byte in frame {
+            buf.extend_from_slice(&[byte]);
+            if let Some(message) = codec.decode(&mut buf).unwrap() {
+                decoded.push(message);
+            }
+        }
+        assert_eq!(decoded, vec![request(1), request(2)]);
+        assert!(codec.decode_eof(&mut buf).unwrap().is_none());
+    }
+
+    #[test]
+    fn truncated_stream() {
+        let mut buf = BytesMut::from("Content-Length: 10\r\n\r\n{}");
+        let mut codec = MessageCodec::new();
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+        assert!(matches!(
+            codec.decode_eof(&mut buf),
+            Err(FramingError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn invalid_headers() {
+        let decode = |frame: &str| MessageCodec::new().decode(&mut BytesMut::from(frame));
+        assert!(matches!(
+            decode("Content-Length 2\r\n\r\n{}"),
+            Err(FramingError::MalformedHeader(_))
+        ));
+        assert!(matches!(
+            decode("Content-Length: 2\n\n{}"),
+            Err(FramingError::MalformedHeader(_))
+        ));
+        assert!(matches!(
+            decode("Content-Type: json\r\n\r\n{}"),
+            Err(FramingError::MissingContentLength)
+        ));
+        assert!(matches!(
+            MessageCodec::new()
+                .with_max_content_length(1)
+                .decode(&mut BytesMut::from("Content-Length: 2\r\n\r\n{}")),
+            Err(FramingError::ContentTooLarge { length: 2, max: 1 })
+        ));
+        let padding = format!("X-Padding: {}", "x".repeat(MAX_HEADER_LENGTH));
+        assert!(matches!(
+            decode(&padding),
+            Err(FramingError::HeaderTooLarge)
+        ));
+    }
+
+    #[test]
+    fn decoding_continues_after_invalid_headers() {
+        let mut codec = MessageCodec::new();
+        let mut buf = BytesMut::from("Content-Length: 2\n\n{}Content-Length: 2\r\n\n{}");
+        codec.encode(&request(1), &mut buf).unwrap();
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(FramingError::MalformedHeader(_))
+        ));
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(FramingError::MalformedHeader(_))
+        ));
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(request(1)));
+
+        // A header that ends after the maximum length is skipped with its content.
+        let padding = format!("X-Padding: {}\r\n", "x".repeat(MAX_HEADER_LENGTH));
+        let mut buf = BytesMut::from(format!("{padding}Content-Length: 2\r\n\r\n{{}}").as_str());
+        codec.encode(&request(2), &mut buf).unwrap();
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(FramingError::HeaderTooLarge)
+        ));
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(request(2)));
+    }
+
+    #[test]
+    fn headers_without_end_are_terminal() {
+        let mut codec = MessageCodec::new();
+        let padding = format!("X-Padding: {}", "x".repeat(MAX_HEADER_LENGTH));
+        let mut buf = BytesMut::from(padding.as_str());
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(FramingError::HeaderTooLarge)
+        ));
+        assert!(buf.is_empty());
+        codec.encode(&request(1), &mut buf).unwrap();
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(FramingError::HeaderTooLarge)
+        ));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn content_of_invalid_messages_is_skipped() {
+        let mut codec = MessageCodec::new().with_max_content_length(100);
+        let mut buf = BytesMut::from("Content-Length: 2\r\nBad header\r\n\r\n{}");
+        codec.encode(&request(1), &mut buf).unwrap();
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(FramingError::MalformedHeader(_))
+        ));
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(request(1)));
+
+        // Content is skipped as it arrives.
+        let header = format!("Content-Length: 200\r\n\r\n{}", " ".repeat(150));
+        let mut buf = BytesMut::from(header.as_str());
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(FramingError::ContentTooLarge { length: 200, .. })
+        ));
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(" ".repeat(50).as_bytes());
+        codec.encode(&request(2), &mut buf).unwrap();
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(request(2)));
+    }
+
+    #[tokio::test]
+    async fn framed_stream_and_sink() {
+        let (client, server) = tokio::io::duplex(16);
+        let writer = tokio::spawn(async move {
+            let mut sink = FramedWrite::new(client, MessageCodec::new());
+            sink.send(request(1)).await.unwrap();
+            let initialized = Message::Event(Box::new(Event::new(2, Events::Initialized(None))));
+            sink.send(&initialized).await.unwrap();
+            sink.into_inner().shutdown().await.unwrap();
+        });
+
+        let mut stream = FramedRead::new(server, MessageCodec::new());
+        assert_eq!(stream.next().await.unwrap().unwrap(), request(1));
+        assert!(matches!(
+            stream.next().await.unwrap().unwrap(),
+            Message::Event(e) if matches!(e.event, Events::Initialized(None))
+        ));
+        assert!(stream.next().await.is_none());
+        writer.await.unwrap();
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::messages::{Request, Response};
+use crate::requests;
+
+/// Allocates sequence numbers for outgoing messages and matches incoming
+/// responses to the requests that are still outstanding.
+///
+/// Every pending request carries a waiter of type `T` (for example a channel
+/// sender), which is handed back when the response arrives.
+#[derive(Debug)]
+pub struct Correlator<T> {
+    next_seq: u64,
+    pending: HashMap<u64, Pending<T>>,
+    /// The last [`RESOLVED_LIMIT`] requests that were answered, oldest first,
+    /// to detect duplicate responses without remembering every request.
+    resolved: VecDeque<u64>,
+}
+
+/// Number of answered requests that a [`Correlator`] remembers.
+pub const RESOLVED_LIMIT: usize = 256;
+
+#[derive(Debug)]
+struct Pending<T> {
+    command: String,
+    waiter: T,
+}
+
+impl<T> Correlator<T> {
+    pub fn new() -> Self {
+        Correlator {
+            next_seq: 1,
+            pending: HashMap::new(),
+            resolved: VecDeque::new(),
+        }
+    }
+
+    /// Returns the sequence number for the next outgoing message.
+    ///
+    /// Sequence numbers are shared by requests, responses and events sent by one
+    /// side, so this should be used for every outgoing message.
+    pub fn next_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq = self.next_seq.saturating_add(1);
+        seq
+    }
+
+    /// Creates request `R` with a fresh sequence number and records it as pending.
+    pub fn request<R: requests::Request>(
+        &mut self,
+        arguments: R::Arguments,
+        waiter: T,
+    ) -> Result<Request, serde_json::Error> {
+        let request = Request::new::<R>(self.next_seq(), arguments)?;
+        self.pending.insert(
+            request.seq,
+            Pending {
+                command: request.command.clone(),
+                waiter,
+            },
+        );
+        Ok(request)
+    }
+
+    /// Records a request that was created elsewhere as pending.
+    pub fn insert(&mut self, request: &Request, waiter: T) -> Result<(), CorrelationError> {
+        if self.pending.contains_key(&request.seq) || self.resolved.contains(&request.seq) {
+            return Err(CorrelationError::DuplicateRequest { seq: request.seq });
+        }
+        self.next_seq = self.next_seq.max(request.seq.saturating_add(1));
+        self.pending.insert(
+            request.seq,
+            Pending {
+                command: request.command.clone(),
+                waiter,
+            },
+        );
+        Ok(())
+    }
+
+    /// Matches a response to its pending request, returning the request's waiter.
+    ///
+    /// If the response has a different command than the request, the request is
+    /// left pending; use [`Correlator::take`] to give up on it.
+    ///
+    /// A second response is reported as a duplicate if the request is one of the
+    /// last [`RESOLVED_LIMIT`] requests answered, and as an orphan otherwise.
+    pub fn resolve(&mut self, response: &Response) -> Result<T, CorrelationError> {
+        let request_seq = response.request_seq;
+        let Some(pending) = self.pending.get(&request_seq) else {
+            if self.resolved.contains(&request_seq) {
+                return Err(CorrelationError::DuplicateResponse { request_seq });
+            }
+            return Err(CorrelationError::Orphan { request_seq });
+        };
+        if pending.command != response.command {
+            return Err(CorrelationError::CommandMismatch {
+                request_seq,
+                expected: pending.command.clone(),
+                actual: response.command.clone(),
+            });
+        }
+        self.take(request_seq)
+            .ok_or(CorrelationError::Orphan { request_seq })
+    }
+
+    /// Removes a pending request without a response, e.g. when it is cancelled.
+    pub fn take(&mut self, seq: u64) -> Option<T> {
+        let pending = self.pending.remove(&seq)?;
+        if self.resolved.len() == RESOLVED_LIMIT {
+            self.resolved.pop_front();
+        }
+        self.resolved.push_back(seq);
+        Some(pending.waiter)
+    }
+
+    /// Returns the command of a pending request.
+    pub fn pending_command(&self, seq: u64) -> Option<&str> {
+        self.pending.get(&seq).map(|p| p.command.as_str())
+    }
+
+    /// Number of requests still waiting for a response.
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Removes all pending requests, e.g. when the connection is closed.
+    pub fn drain(&mut self) -> impl Iterator<Item = (u64, T)> + '_ {
+        self.resolved.clear();
+        self.pending.drain().map(|(seq, p)| (seq, p.waiter))
+    }
+}
+
+impl<T> Default for Correlator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Protocol violations detected while correlating requests and responses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorrelationError {
+    /// Response refers to a request that was never sent.
+    Orphan { request_seq: u64 },
+    /// Second response to a request that was already answered.
+    DuplicateResponse { request_seq: u64 },
+    /// Request reuses the sequence number of an earlier request.
+    DuplicateRequest { seq: u64 },
+    /// Response has a different command than the request it answers.
+    CommandMismatch {
+        request_seq: u64,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl std::fmt::Display for CorrelationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CorrelationError::Orphan { request_seq } => {
+                write!(f, "response to unknown request {request_seq}")
+            }
+            CorrelationError::DuplicateResponse { request_seq } => {
+                write!(f, "duplicate response to request {request_seq}")
+            }
+            CorrelationError::DuplicateRequest { seq } => {
+                write!(f, "duplicate request seq {seq}")
+            }
+            CorrelationError::CommandMismatch {
+                request_seq,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "response to request {request_seq} has command {actual:?}, expected {expected:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CorrelationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::requests::Request as _;
+
+    fn response(request_seq: u64, command: &str) -> Response {
+        Response {
+            seq: 100,
+            request_seq,
+            success: true,
+            command: command.to_owned(),
+            body: None,
+            message: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn routes_responses_to_waiters() {
+        let mut correlator = Correlator::new();
+        let threads = correlator
+            .request::<requests::Threads>(crate::ThreadsArgument::default(), "threads")
+            .unwrap();
+        let pause = correlator
+            .request::<requests::Pause>(
+                crate::PauseArguments {
+                    thread_id: 1,
+                    #[cfg(feature = "extra-fields")]
+                    extra: Default::default(),
+                },
+                "pause",
+            )
+            .unwrap();
+        assert_eq!((threads.seq, pause.seq), (1, 2));
+        assert_eq!(correlator.next_seq(), 3);
+        assert_eq!(correlator.pending_count(), 2);
+
+        let command = requests::Pause::COMMAND;
+        assert_eq!(correlator.resolve(&response(2, command)), Ok("pause"));
+        assert_eq!(correlator.resolve(&response(1, "threads")), Ok("threads"));
+        assert_eq!(correlator.pending_count(), 0);
+    }
+
+    #[test]
+    fn reports_protocol_errors() {
+        let mut correlator = Correlator::new();
+        let request = correlator
+            .request::<requests::Threads>(crate::ThreadsArgument::default(), ())
+            .unwrap();
+        let later = correlator
+            .request::<requests::Threads>(crate::ThreadsArgument::default(), ())
+            .unwrap();
+
+        assert_eq!(
+            correlator.resolve(&response(7, "threads")),
+            Err(CorrelationError::Orphan { request_seq: 7 })
+        );
+        assert_eq!(
+            correlator.resolve(&response(1, "scopes")),
+            Err(CorrelationError::CommandMismatch {
+                request_seq: 1,
+                expected: "threads".to_owned(),
+                actual: "scopes".to_owned(),
+            })
+        );
+        assert_eq!(correlator.pending_command(1), Some("threads"));
+        assert_eq!(correlator.resolve(&response(2, "threads")), Ok(()));
+        assert_eq!(
+            correlator.resolve(&response(2, "threads")),
+            Err(CorrelationError::DuplicateResponse { request_seq: 2 })
+        );
+        assert_eq!(
+            correlator.insert(&request, ()),
+            Err(CorrelationError::DuplicateRequest { seq: 1 })
+        );
+        assert_eq!(
+            correlator.insert(&later, ()),
+            Err(CorrelationError::DuplicateRequest { seq: 2 })
+        );
+        assert_eq!(correlator.resolve(&response(1, "threads")), Ok(()));
+    }
+
+    #[test]
+    fn remembers_recently_answered_requests() {
+        let mut correlator = Correlator::new();
+        correlator
+            .request::<requests::Threads>(crate::ThreadsArgument::default(), ())
+            .unwrap();
+        // Requests answered in order are still remembered.
+        assert_eq!(correlator.resolve(&response(1, "threads")), Ok(()));
+        assert_eq!(
+            correlator.resolve(&response(1, "threads")),
+            Err(CorrelationError::DuplicateResponse { request_seq: 1 })
+        );
+
+        for seq in 2..=RESOLVED_LIMIT as u64 + 1 {
+            correlator
+                .request::<requests::Threads>(crate::ThreadsArgument::default(), ())
+                .unwrap();
+            correlator.take(seq);
+        }
+        assert_eq!(correlator.resolved.len(), RESOLVED_LIMIT);
+        assert_eq!(
+            correlator.resolve(&response(1, "threads")),
+            Err(CorrelationError::Orphan { request_seq: 1 })
+        );
+        assert_eq!(
+            correlator.resolve(&response(2, "threads")),
+            Err(CorrelationError::DuplicateResponse { request_seq: 2 })
+        );
+    }
+
+    #[test]
+    fn inserted_requests_advance_seq() {
+        let mut correlator = Correlator::new();
+        let request = Request {
+            seq: 10,
+            command: "threads".to_owned(),
+            arguments: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        };
+        correlator.insert(&request, ()).unwrap();
+        assert_eq!(correlator.next_seq(), 11);
+        assert_eq!(correlator.take(10), Some(()));
+        assert_eq!(correlator.take(10), None);
+
+        let last = Request {
+            seq: u64::MAX,
+            ..request
+        };
+        correlator.insert(&last, ()).unwrap();
+        assert_eq!(correlator.next_seq(), u64::MAX);
+        assert_eq!(correlator.next_seq(), u64::MAX);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::sync::mpsc;
+
+use crate::events::Event;
+use crate::messages::{Events, OtherEvent};
+
+type Handler = Box<dyn FnMut(&Events) -> bool + Send>;
+type OtherHandler = Box<dyn FnMut(&OtherEvent) -> bool + Send>;
+
+/// Fans out events to subscribers registered per event type.
+///
+/// Subscribers are either callbacks, registered with [`EventDispatcher::on`],
+/// or channels, created with [`EventDispatcher::channel`]. A channel stops
+/// receiving events once its receiver is dropped. Events that are not defined
+/// by the protocol go to the subscribers registered with
+/// [`EventDispatcher::on_other`].
+#[derive(Default)]
+pub struct EventDispatcher {
+    handlers: HashMap<&'static str, Vec<Handler>>,
+    other: Vec<OtherHandler>,
+}
+
+impl EventDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `callback` with the body of every event `E`.
+    pub fn on<E: Event>(&mut self, mut callback: impl FnMut(&E::Body) + Send + 'static) {
+        self.subscribe::<E>(move |body| {
+            callback(body);
+            true
+        });
+    }
+
+    /// Returns a channel receiving the body of every event `E`.
+    pub fn channel<E: Event>(&mut self) -> mpsc::Receiver<E::Body>
+    where
+        E::Body: 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribe::<E>(move |body| sender.send(body.clone()).is_ok());
+        receiver
+    }
+
+    /// Calls `callback` with every event that is not defined by the protocol, or
+    /// that has a body that does not match the event.
+    pub fn on_other(&mut self, mut callback: impl FnMut(&OtherEvent) + Send + 'static) {
+        self.other.push(Box::new(move |event| {
+            callback(event);
+            true
+        }));
+    }
+
+    /// Passes `event` to its subscribers, returning whether there were any.
+    pub fn dispatch(&mut self, event: &Events) -> bool {
+        if let Events::Other(other) = event {
+            self.other.retain_mut(|handler| handler(other));
+            return !self.other.is_empty();
+        }
+        let Some(handlers) = self.handlers.get_mut(event.name()) else {
+            return false;
+        };
+        handlers.retain_mut(|handler| handler(event));
+        !handlers.is_empty()
+    }
+
+    /// Adds a subscriber for event `E`, which is removed when it returns false.
+    fn subscribe<E: Event>(&mut self, mut handler: impl FnMut(&E::Body) -> bool + Send + 'static) {
+        let handler = move |event: &Events| match E::from_events(event) {
+            Some(body) => handler(body),
+            None => true,
+        };
+        self.handlers
+            .entry(E::EVENT)
+            .or_default()
+            .push(Box::new(handler));
+    }
+}
+
+impl std::fmt::Debug for EventDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut events = self.handlers.keys().collect::<Vec<_>>();
+        events.sort();
+        f.debug_struct("EventDispatcher")
+            .field("events", &events)
+            .field("other", &self.other.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    fn stopped(thread_id: i64) -> Events {
+        let body = json!({ "reason": "breakpoint", "threadId": thread_id });
+        Events::new::<events::Stopped>(serde_json::from_value(body).unwrap())
+    }
+
+    #[test]
+    fn calls_subscribers_of_event_type() {
+        let mut dispatcher = EventDispatcher::new();
+        let stopped_threads = Arc::new(Mutex::new(Vec::new()));
+        let threads = stopped_threads.clone();
+        dispatcher.on::<events::Stopped>(move |body| threads.lock().unwrap().push(body.thread_id));
+        let exited = dispatcher.channel::<events::Exited>();
+
+        assert!(dispatcher.dispatch(&stopped(1)));
+        assert!(dispatcher.dispatch(&stopped(2)));
+        let exit = Events::new::<events::Exited>(crate::ExitedEvent {
+            exit_code: 3,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        });
+        assert!(dispatcher.dispatch(&exit));
+        assert!(!dispatcher.dispatch(&Events::new::<events::Initialized>(None)));
+
+        assert_eq!(*stopped_threads.lock().unwrap(), [Some(1), Some(2)]);
+        assert_eq!(
+            exited.try_iter().map(|e| e.exit_code).collect::<Vec<_>>(),
+            [3]
+        );
+    }
+
+    #[test]
+    fn other_events_and_dropped_channels() {
+        let mut dispatcher = EventDispatcher::new();
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let other_names = names.clone();
+        dispatcher.on_other(move |event| other_names.lock().unwrap().push(event.event.clone()));
+        let stopped_events = dispatcher.channel::<events::Stopped>();
+
+        let custom = serde_json::from_value(json!({ "event": "custom", "body": {} }));
+        assert!(dispatcher.dispatch(&custom.unwrap()));
+        assert_eq!(*names.lock().unwrap(), ["custom"]);
+
+        assert!(dispatcher.dispatch(&stopped(1)));
+        drop(stopped_events);
+        assert!(!dispatcher.dispatch(&stopped(2)));
+    }
+}
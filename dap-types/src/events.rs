@@ -1,13 +1,24 @@
 // This file is autogenerated. Do not edit by hand.
 // To regenerate from schema, run `cargo run -p generator`.
 
-use serde::{de::DeserializeOwned, Serialize};
+#![allow(clippy::doc_lazy_continuation)]
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
 use std::fmt::Debug;
 
+use crate::messages::OtherEvent;
+
 /// Event is an event, with associated name and body type.
 pub trait Event {
     const EVENT: &'static str;
     type Body: Debug + Clone + Serialize + DeserializeOwned + Send + Sync;
+
+    /// Wraps a body of this event in the matching [`Events`] variant.
+    fn into_events(body: Self::Body) -> Events;
+
+    /// Returns the body if `events` is this event.
+    fn from_events(events: &Events) -> Option<&Self::Body>;
 }
 
 /// This event indicates that the debug adapter is ready to accept configuration requests (e.g. `setBreakpoints`, `setExceptionBreakpoints`).
@@ -24,6 +35,17 @@ pub enum Initialized {}
 impl Event for Initialized {
     const EVENT: &'static str = "initialized";
     type Body = Option<crate::Capabilities>;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Initialized(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Initialized(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that the execution of the debuggee has stopped due to some condition.
@@ -33,6 +55,17 @@ pub enum Stopped {}
 impl Event for Stopped {
     const EVENT: &'static str = "stopped";
     type Body = crate::StoppedEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Stopped(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Stopped(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that the execution of the debuggee has continued.
@@ -43,6 +76,17 @@ pub enum Continued {}
 impl Event for Continued {
     const EVENT: &'static str = "continued";
     type Body = crate::ContinuedEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Continued(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Continued(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that the debuggee has exited and returns its exit code.
@@ -51,6 +95,17 @@ pub enum Exited {}
 impl Event for Exited {
     const EVENT: &'static str = "exited";
     type Body = crate::ExitedEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Exited(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Exited(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that debugging of the debuggee has terminated. This does **not** mean that the debuggee itself has exited.
@@ -58,7 +113,18 @@ pub enum Terminated {}
 
 impl Event for Terminated {
     const EVENT: &'static str = "terminated";
-    type Body = crate::TerminatedEvent;
+    type Body = Option<crate::TerminatedEvent>;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Terminated(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Terminated(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that a thread has started or exited.
@@ -67,6 +133,17 @@ pub enum Thread {}
 impl Event for Thread {
     const EVENT: &'static str = "thread";
     type Body = crate::ThreadEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Thread(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Thread(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that the target has produced some output.
@@ -75,6 +152,17 @@ pub enum Output {}
 impl Event for Output {
     const EVENT: &'static str = "output";
     type Body = crate::OutputEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Output(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Output(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that some information about a breakpoint has changed.
@@ -83,6 +171,17 @@ pub enum Breakpoint {}
 impl Event for Breakpoint {
     const EVENT: &'static str = "breakpoint";
     type Body = crate::BreakpointEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Breakpoint(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Breakpoint(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that some information about a module has changed.
@@ -91,6 +190,17 @@ pub enum Module {}
 impl Event for Module {
     const EVENT: &'static str = "module";
     type Body = crate::ModuleEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Module(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Module(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that some source has been added, changed, or removed from the set of all loaded sources.
@@ -99,6 +209,17 @@ pub enum LoadedSource {}
 impl Event for LoadedSource {
     const EVENT: &'static str = "loadedSource";
     type Body = crate::LoadedSourceEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::LoadedSource(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::LoadedSource(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that the debugger has begun debugging a new process. Either one that it has launched, or one that it has attached to.
@@ -107,6 +228,17 @@ pub enum Process {}
 impl Event for Process {
     const EVENT: &'static str = "process";
     type Body = crate::ProcessEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Process(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Process(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event indicates that one or more capabilities have changed.
@@ -118,6 +250,17 @@ pub enum Capabilities {}
 impl Event for Capabilities {
     const EVENT: &'static str = "capabilities";
     type Body = crate::CapabilitiesEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Capabilities(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Capabilities(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event signals that a long running operation is about to start and provides additional information for the client to set up a corresponding progress and cancellation UI.
@@ -128,6 +271,17 @@ pub enum ProgressStart {}
 impl Event for ProgressStart {
     const EVENT: &'static str = "progressStart";
     type Body = crate::ProgressStartEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::ProgressStart(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::ProgressStart(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event signals that the progress reporting needs to be updated with a new message and/or percentage.
@@ -138,6 +292,17 @@ pub enum ProgressUpdate {}
 impl Event for ProgressUpdate {
     const EVENT: &'static str = "progressUpdate";
     type Body = crate::ProgressUpdateEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::ProgressUpdate(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::ProgressUpdate(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// The event signals the end of the progress reporting with a final message.
@@ -147,6 +312,17 @@ pub enum ProgressEnd {}
 impl Event for ProgressEnd {
     const EVENT: &'static str = "progressEnd";
     type Body = crate::ProgressEndEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::ProgressEnd(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::ProgressEnd(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// This event signals that some state in the debug adapter has changed and requires that the client needs to re-render the data snapshot previously requested.
@@ -157,6 +333,17 @@ pub enum Invalidated {}
 impl Event for Invalidated {
     const EVENT: &'static str = "invalidated";
     type Body = crate::InvalidatedEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Invalidated(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Invalidated(body) => Some(body),
+            _ => None,
+        }
+    }
 }
 
 /// This event indicates that some memory range has been updated. It should only be sent if the corresponding capability `supportsMemoryEvent` is true.
@@ -167,4 +354,150 @@ pub enum Memory {}
 impl Event for Memory {
     const EVENT: &'static str = "memory";
     type Body = crate::MemoryEvent;
+
+    fn into_events(body: Self::Body) -> Events {
+        Events::Memory(body)
+    }
+
+    fn from_events(events: &Events) -> Option<&Self::Body> {
+        match events {
+            Events::Memory(body) => Some(body),
+            _ => None,
+        }
+    }
+}
+
+/// An event with a typed body.
+/// Events not defined by the protocol, or with a body that does not match the event, are decoded as `Other`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "event", content = "body")]
+#[serde(rename_all = "camelCase")]
+pub enum Events {
+    Initialized(Option<crate::Capabilities>),
+    Stopped(crate::StoppedEvent),
+    Continued(crate::ContinuedEvent),
+    Exited(crate::ExitedEvent),
+    Terminated(Option<crate::TerminatedEvent>),
+    Thread(crate::ThreadEvent),
+    Output(crate::OutputEvent),
+    Breakpoint(crate::BreakpointEvent),
+    Module(crate::ModuleEvent),
+    LoadedSource(crate::LoadedSourceEvent),
+    Process(crate::ProcessEvent),
+    Capabilities(crate::CapabilitiesEvent),
+    ProgressStart(crate::ProgressStartEvent),
+    ProgressUpdate(crate::ProgressUpdateEvent),
+    ProgressEnd(crate::ProgressEndEvent),
+    Invalidated(crate::InvalidatedEvent),
+    Memory(crate::MemoryEvent),
+    #[serde(untagged)]
+    Other(OtherEvent),
+}
+
+impl Events {
+    /// Name of the event, as sent in the `event` field.
+    pub fn name(&self) -> &str {
+        match self {
+            Events::Initialized(_) => Initialized::EVENT,
+            Events::Stopped(_) => Stopped::EVENT,
+            Events::Continued(_) => Continued::EVENT,
+            Events::Exited(_) => Exited::EVENT,
+            Events::Terminated(_) => Terminated::EVENT,
+            Events::Thread(_) => Thread::EVENT,
+            Events::Output(_) => Output::EVENT,
+            Events::Breakpoint(_) => Breakpoint::EVENT,
+            Events::Module(_) => Module::EVENT,
+            Events::LoadedSource(_) => LoadedSource::EVENT,
+            Events::Process(_) => Process::EVENT,
+            Events::Capabilities(_) => Capabilities::EVENT,
+            Events::ProgressStart(_) => ProgressStart::EVENT,
+            Events::ProgressUpdate(_) => ProgressUpdate::EVENT,
+            Events::ProgressEnd(_) => ProgressEnd::EVENT,
+            Events::Invalidated(_) => Invalidated::EVENT,
+            Events::Memory(_) => Memory::EVENT,
+            Events::Other(other) => &other.event,
+        }
+    }
+}
+
+impl std::fmt::Display for Events {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Events::Initialized(_) => write!(f, "Initialized"),
+            Events::Stopped(_) => write!(f, "Stopped"),
+            Events::Continued(_) => write!(f, "Continued"),
+            Events::Exited(_) => write!(f, "Exited"),
+            Events::Terminated(_) => write!(f, "Terminated"),
+            Events::Thread(_) => write!(f, "Thread"),
+            Events::Output(_) => write!(f, "Output"),
+            Events::Breakpoint(_) => write!(f, "Breakpoint"),
+            Events::Module(_) => write!(f, "Module"),
+            Events::LoadedSource(_) => write!(f, "LoadedSource"),
+            Events::Process(_) => write!(f, "Process"),
+            Events::Capabilities(_) => write!(f, "Capabilities"),
+            Events::ProgressStart(_) => write!(f, "ProgressStart"),
+            Events::ProgressUpdate(_) => write!(f, "ProgressUpdate"),
+            Events::ProgressEnd(_) => write!(f, "ProgressEnd"),
+            Events::Invalidated(_) => write!(f, "Invalidated"),
+            Events::Memory(_) => write!(f, "Memory"),
+            Events::Other(other) => write!(f, "{}", other.event.as_str()),
+        }
+    }
+}
+
+/// Checks that an event body fits the body type of the event.
+/// Returns `None` for events not defined by the protocol.
+pub(crate) fn check_event_body(event: &str, body: Value) -> Option<Result<(), serde_json::Error>> {
+    fn check<E: Event>(body: Value) -> Result<(), serde_json::Error> {
+        serde_json::from_value::<E::Body>(body).map(drop)
+    }
+    Some(match event {
+        "initialized" => check::<Initialized>(body),
+        "stopped" => check::<Stopped>(body),
+        "continued" => check::<Continued>(body),
+        "exited" => check::<Exited>(body),
+        "terminated" => check::<Terminated>(body),
+        "thread" => check::<Thread>(body),
+        "output" => check::<Output>(body),
+        "breakpoint" => check::<Breakpoint>(body),
+        "module" => check::<Module>(body),
+        "loadedSource" => check::<LoadedSource>(body),
+        "process" => check::<Process>(body),
+        "capabilities" => check::<Capabilities>(body),
+        "progressStart" => check::<ProgressStart>(body),
+        "progressUpdate" => check::<ProgressUpdate>(body),
+        "progressEnd" => check::<ProgressEnd>(body),
+        "invalidated" => check::<Invalidated>(body),
+        "memory" => check::<Memory>(body),
+        _ => return None,
+    })
+}
+
+/// Writes the body of an event to the map of its message.
+/// Events without a body are encoded without one, instead of `null`.
+pub(crate) fn serialize_event_body<M: serde::ser::SerializeMap>(
+    event: &Events,
+    map: &mut M,
+) -> Result<(), M::Error> {
+    match event {
+        Events::Initialized(Some(body)) => map.serialize_entry("body", body),
+        Events::Stopped(body) => map.serialize_entry("body", body),
+        Events::Continued(body) => map.serialize_entry("body", body),
+        Events::Exited(body) => map.serialize_entry("body", body),
+        Events::Terminated(Some(body)) => map.serialize_entry("body", body),
+        Events::Thread(body) => map.serialize_entry("body", body),
+        Events::Output(body) => map.serialize_entry("body", body),
+        Events::Breakpoint(body) => map.serialize_entry("body", body),
+        Events::Module(body) => map.serialize_entry("body", body),
+        Events::LoadedSource(body) => map.serialize_entry("body", body),
+        Events::Process(body) => map.serialize_entry("body", body),
+        Events::Capabilities(body) => map.serialize_entry("body", body),
+        Events::ProgressStart(body) => map.serialize_entry("body", body),
+        Events::ProgressUpdate(body) => map.serialize_entry("body", body),
+        Events::ProgressEnd(body) => map.serialize_entry("body", body),
+        Events::Invalidated(body) => map.serialize_entry("body", body),
+        Events::Memory(body) => map.serialize_entry("body", body),
+        Events::Other(other) if !other.body.is_null() => map.serialize_entry("body", &other.body),
+        _ => Ok(()),
+    }
 }
@@ -0,0 +1,142 @@
+use serde::de::value::MapDeserializer;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// A protocol type that keeps the fields it doesn't define.
+///
+/// Implemented by every generated struct.
+pub trait Extensible {
+    /// Fields that are not defined by the protocol.
+    fn extra(&self) -> &Map<String, Value>;
+
+    fn extra_mut(&mut self) -> &mut Map<String, Value>;
+
+    /// Decodes extension `X` from the fields that are not defined by the protocol.
+    fn ext<X: Extension<Self>>(&self) -> Result<X, serde_json::Error> {
+        let fields = self
+            .extra()
+            .iter()
+            .map(|(name, value)| (name.as_str(), value));
+        X::deserialize(MapDeserializer::new(fields))
+    }
+
+    /// Encodes extension `X` into the fields that are not defined by the
+    /// protocol, replacing those with the same names.
+    fn set_ext<X: Extension<Self>>(&mut self, ext: &X) -> Result<(), serde_json::Error> {
+        match serde_json::to_value(ext)? {
+            Value::Object(fields) => {
+                self.extra_mut().extend(fields);
+                Ok(())
+            }
+            _ => Err(serde::ser::Error::custom("extension is not an object")),
+        }
+    }
+}
+
+/// Vendor fields that an adapter adds to protocol type `T`.
+///
+/// Implementing `Extension<StackFrame>` for a struct with the fields an adapter
+/// adds to stack frames lets them be read with `frame.ext::<X>()`. Fields of
+/// the extension should be optional, so that frames from other adapters still
+/// decode.
+pub trait Extension<T: ?Sized>: Serialize + DeserializeOwned {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct LldbFrame {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        module_name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        is_artificial: Option<bool>,
+    }
+
+    impl Extension<crate::StackFrame> for LldbFrame {}
+
+    fn frame(fields: Value) -> crate::StackFrame {
+        let mut frame = json!({ "id": 1, "name": "main", "line": 3, "column": 1 });
+        frame
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(frame).unwrap()
+    }
+
+    #[test]
+    fn reads_extension_fields() {
+        let lldb = frame(json!({ "moduleName": "a.out", "isArtificial": false }));
+        assert_eq!(
+            lldb.ext::<LldbFrame>().unwrap(),
+            LldbFrame {
+                module_name: Some("a.out".to_owned()),
+                is_artificial: Some(false),
+            }
+        );
+
+        let other = frame(json!({}));
+        assert_eq!(
+            other.ext::<LldbFrame>().unwrap(),
+            LldbFrame {
+                module_name: None,
+                is_artificial: None,
+            }
+        );
+        let invalid = frame(json!({ "isArtificial": "no" }));
+        assert!(invalid.ext::<LldbFrame>().is_err());
+    }
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct SignalStop {
+        signal_name: Option<String>,
+    }
+
+    impl Extension<crate::StoppedEvent> for SignalStop {}
+
+    #[test]
+    fn reads_vendor_stop_reason() {
+        let stopped: crate::StoppedEvent = serde_json::from_value(json!({
+            "reason": "signal",
+            "threadId": 1,
+            "signalName": "SIGSEGV",
+        }))
+        .unwrap();
+        assert_eq!(
+            stopped.reason,
+            crate::StoppedEventReason::Other("signal".to_owned())
+        );
+        assert_eq!(
+            stopped.ext::<SignalStop>().unwrap().signal_name.as_deref(),
+            Some("SIGSEGV")
+        );
+    }
+
+    #[test]
+    fn writes_extension_fields() {
+        let mut frame = frame(json!({ "moduleName": "a.out", "vendorId": 7 }));
+        let ext = LldbFrame {
+            module_name: Some("b.out".to_owned()),
+            is_artificial: Some(true),
+        };
+        frame.set_ext(&ext).unwrap();
+        assert_eq!(frame.ext::<LldbFrame>().unwrap(), ext);
+        assert_eq!(
+            serde_json::to_value(&frame).unwrap(),
+            json!({
+                "id": 1,
+                "name": "main",
+                "line": 3,
+                "column": 1,
+                "moduleName": "b.out",
+                "isArtificial": true,
+                "vendorId": 7,
+            })
+        );
+    }
+}
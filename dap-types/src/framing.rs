@@ -0,0 +1,465 @@
+use std::io::{BufRead, BufReader, Read, Write};
+
+use crate::messages::Message;
+
+/// Default upper bound for the `Content-Length` of a single message (64 MiB).
+pub const DEFAULT_MAX_CONTENT_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Upper bound for the total size of the header part of a single message.
+pub const MAX_HEADER_LENGTH: usize = 8 * 1024;
+
+const CONTENT_LENGTH: &str = "Content-Length";
+
+/// Errors that can occur while reading or writing framed messages.
+#[derive(Debug)]
+pub enum FramingError {
+    /// Underlying reader or writer failed.
+    Io(std::io::Error),
+    /// Stream ended in the middle of a message.
+    UnexpectedEof,
+    /// Header line is not valid UTF-8, or is not of the form `Name: value`.
+    MalformedHeader(String),
+    /// Header part of the message did not contain `Content-Length`.
+    MissingContentLength,
+    /// `Content-Length` header was given more than once with different values.
+    DuplicateContentLength,
+    /// `Content-Length` value is not a valid length.
+    InvalidContentLength(String),
+    /// `Content-Length` exceeds the configured maximum.
+    ContentTooLarge { length: usize, max: usize },
+    /// Header part of the message exceeds [`MAX_HEADER_LENGTH`].
+    HeaderTooLarge,
+    /// Message content is not a valid protocol message.
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for FramingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FramingError::Io(e) => write!(f, "io error: {e}"),
+            FramingError::UnexpectedEof => write!(f, "unexpected end of stream"),
+            FramingError::MalformedHeader(header) => write!(f, "malformed header: {header:?}"),
+            FramingError::MissingContentLength => write!(f, "missing Content-Length header"),
+            FramingError::DuplicateContentLength => {
+                write!(f, "conflicting Content-Length headers")
+            }
+            FramingError::InvalidContentLength(value) => {
+                write!(f, "invalid Content-Length: {value:?}")
+            }
+            FramingError::ContentTooLarge { length, max } => {
+                write!(f, "content length {length} exceeds maximum of {max}")
+            }
+            FramingError::HeaderTooLarge => {
+                write!(f, "header exceeds maximum of {MAX_HEADER_LENGTH} bytes")
+            }
+            FramingError::Json(e) => write!(f, "invalid message: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for FramingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FramingError::Io(e) => Some(e),
+            FramingError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for FramingError {
+    fn from(e: std::io::Error) -> Self {
+        FramingError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for FramingError {
+    fn from(e: serde_json::Error) -> Self {
+        FramingError::Json(e)
+    }
+}
+
+/// Parses a single header line (without the trailing `\r\n`).
+///
+/// Returns the content length if this is a `Content-Length` header, `None` for
+/// any other well-formed header.
+pub(crate) fn parse_header_line(
+    line: &[u8],
+    max_content_length: usize,
+) -> Result<Option<usize>, FramingError> {
+    let line = std::str::from_utf8(line)
+        .map_err(|_| FramingError::MalformedHeader(String::from_utf8_lossy(line).into_owned()))?;
+    let Some((name, value)) = line.split_once(':') else {
+        return Err(FramingError::MalformedHeader(line.to_owned()));
+    };
+    if name.is_empty() || name.trim() != name {
+        return Err(FramingError::MalformedHeader(line.to_owned()));
+    }
+    if !name.eq_ignore_ascii_case(CONTENT_LENGTH) {
+        return Ok(None);
+    }
+    let value = value.trim();
+    let length = value
+        .parse::<usize>()
+        .map_err(|_| FramingError::InvalidContentLength(value.to_owned()))?;
+    if length > max_content_length {
+        return Err(FramingError::ContentTooLarge {
+            length,
+            max: max_content_length,
+        });
+    }
+    Ok(Some(length))
+}
+
+/// Records a parsed `Content-Length`, rejecting conflicting duplicates.
+pub(crate) fn set_content_length(
+    slot: &mut Option<usize>,
+    length: usize,
+) -> Result<(), FramingError> {
+    match *slot {
+        Some(existing) if existing != length => Err(FramingError::DuplicateContentLength),
+        _ => {
+            *slot = Some(length);
+            Ok(())
+        }
+    }
+}
+
+/// Serializes a message together with its `Content-Length` header.
+pub(crate) fn encode_message(message: &Message) -> Result<Vec<u8>, FramingError> {
+    let content = serde_json::to_vec(message)?;
+    let mut frame = format!("{CONTENT_LENGTH}: {}\r\n\r\n", content.len()).into_bytes();
+    frame.extend_from_slice(&content);
+    Ok(frame)
+}
+
+/// Reads `Content-Length` framed messages from a reader, which is buffered
+/// internally.
+pub struct MessageReader<R> {
+    reader: BufReader<R>,
+    max_content_length: usize,
+    line: Vec<u8>,
+}
+
+impl<R: Read> MessageReader<R> {
+    pub fn new(reader: R) -> Self {
+        MessageReader {
+            reader: BufReader::new(reader),
+            max_content_length: DEFAULT_MAX_CONTENT_LENGTH,
+            line: Vec::new(),
+        }
+    }
+
+    /// Sets the largest `Content-Length` that will be accepted.
+    pub fn with_max_content_length(mut self, max_content_length: usize) -> Self {
+        self.max_content_length = max_content_length;
+        self
+    }
+
+    pub fn get_ref(&self) -> &R {
+        self.reader.get_ref()
+    }
+
+    /// Returns the underlying reader. Data that was buffered but not read yet
+    /// is lost.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+
+    /// Reads the next message.
+    ///
+    /// Returns `Ok(None)` if the stream ended cleanly between two messages.
+    pub fn read(&mut self) -> Result<Option<Message>, FramingError> {
+        let Some(content) = self.read_content()? else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::from_slice(&content)?))
+    }
+
+    /// Reads the raw content of the next message, without decoding it.
+    ///
+    /// An invalid header is read up to its end, and if it still has a
+    /// `Content-Length`, so is the content of that message, so that the next
+    /// call reads the next message.
+    pub fn read_content(&mut self) -> Result<Option<Vec<u8>>, FramingError> {
+        let mut content_length = None;
+        let mut header_length = 0;
+        let mut error = None;
+        loop {
+            self.line.clear();
+            // Lines are read in chunks, so that a header that is too large can
+            // still be read up to its end.
+            let read = (&mut self.reader)
+                .take(MAX_HEADER_LENGTH as u64 + 1)
+                .read_until(b'\n', &mut self.line)?;
+            if read == 0 {
+                if header_length == 0 {
+                    return Ok(None);
+                }
+                return Err(error.unwrap_or(FramingError::UnexpectedEof));
+            }
+            header_length += read;
+            if header_length > MAX_HEADER_LENGTH {
+                error.get_or_insert(FramingError::HeaderTooLarge);
+            }
+            let line = match self.line.strip_suffix(b"\r\n") {
+                Some(line) => line,
+                None => match self.line.strip_suffix(b"\n") {
+                    // Lines ending with `\n` are invalid, but can still end the
+                    // header or give the length of the content to skip.
+                    Some(line) => {
+                        error.get_or_insert(FramingError::MalformedHeader(
+                            String::from_utf8_lossy(&self.line).into_owned(),
+                        ));
+                        line
+                    }
+                    None if read > MAX_HEADER_LENGTH => continue,
+                    None => return Err(error.unwrap_or(FramingError::UnexpectedEof)),
+                },
+            };
+            if line.is_empty() {
+                break;
+            }
+            match parse_header_line(line, usize::MAX) {
+                Ok(Some(length)) => {
+                    if let Err(e) = set_content_length(&mut content_length, length) {
+                        error.get_or_insert(e);
+                    }
+                    if length > self.max_content_length {
+                        error.get_or_insert(FramingError::ContentTooLarge {
+                            length,
+                            max: self.max_content_length,
+                        });
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        let Some(length) = content_length else {
+            return Err(error.unwrap_or(FramingError::MissingContentLength));
+        };
+        if let Some(error) = error {
+            // If the stream ends first, the next call reports the end.
+            std::io::copy(
+                &mut (&mut self.reader).take(length as u64),
+                &mut std::io::sink(),
+            )?;
+            return Err(error);
+        }
+        let mut content = vec![0; length];
+        self.reader.read_exact(&mut content).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                FramingError::UnexpectedEof
+            } else {
+                FramingError::Io(e)
+            }
+        })?;
+        Ok(Some(content))
+    }
+}
+
+impl<R: Read> Iterator for MessageReader<R> {
+    type Item = Result<Message, FramingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
+
+/// Writes `Content-Length` framed messages to a writer.
+pub struct MessageWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> MessageWriter<W> {
+    pub fn new(writer: W) -> Self {
+        MessageWriter { writer }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes a single message and flushes the underlying writer.
+    pub fn write(&mut self, message: &Message) -> Result<(), FramingError> {
+        let frame = encode_message(message)?;
+        self.writer.write_all(&frame)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes already encoded message content, e.g. from
+    /// [`MessageReader::read_content`], and flushes the underlying writer.
+    pub fn write_content(&mut self, content: &[u8]) -> Result<(), FramingError> {
+        write!(self.writer, "{CONTENT_LENGTH}: {}\r\n\r\n", content.len())?;
+        self.writer.write_all(content)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{Events, Request};
+    use serde_json::json;
+
+    fn request(seq: u64) -> Message {
+        Message::Request(Request {
+            seq,
+            command: "pause".to_owned(),
+            arguments: Some(json!({ "threadId": 1 })),
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+    }
+
+    /// Reader that returns at most one byte per `read` call.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut writer = MessageWriter::new(Vec::new());
+        writer.write(&request(1)).unwrap();
+        writer.write(&request(2)).unwrap();
+        let bytes = writer.into_inner();
+
+        let reader = MessageReader::new(Trickle(&bytes));
+        let messages = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(messages, vec![request(1), request(2)]);
+    }
+
+    #[test]
+    fn raw_content_round_trip() {
+        let content = br#"{"seq":1,"type":"request","command":"threads","extra":true}"#;
+        let mut writer = MessageWriter::new(Vec::new());
+        writer.write_content(content).unwrap();
+        let bytes = writer.into_inner();
+
+        let mut reader = MessageReader::new(&bytes[..]);
+        assert_eq!(reader.read_content().unwrap().unwrap(), content);
+        assert!(reader.read_content().unwrap().is_none());
+    }
+
+    #[test]
+    fn extra_headers_are_ignored() {
+        let content = json!({
+            "seq": 3,
+            "type": "event",
+            "event": "initialized",
+        })
+        .to_string();
+        let frame = format!(
+            "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\ncontent-length: {}\r\n\r\n{content}",
+            content.len()
+        );
+        let mut reader = MessageReader::new(frame.as_bytes());
+        let message = reader.read().unwrap().unwrap();
+        assert!(
+            matches!(message, Message::Event(e) if matches!(e.event, Events::Initialized(None)))
+        );
+        assert!(reader.read().unwrap().is_none());
+    }
+
+    #[test]
+    fn malformed_headers() {
+        let read = |frame: &str| MessageReader::new(frame.as_bytes()).read().unwrap_err();
+        assert!(matches!(
+            read("Content-Length 2\r\n\r\n{}"),
+            FramingError::MalformedHeader(_)
+        ));
+        assert!(matches!(
+            read("Content-Length: 2\n\n{}"),
+            FramingError::MalformedHeader(_)
+        ));
+        assert!(matches!(
+            read("Content-Length: two\r\n\r\n{}"),
+            FramingError::InvalidContentLength(_)
+        ));
+        assert!(matches!(
+            read("Content-Length: 2\r\nContent-Length: 3\r\n\r\n{}"),
+            FramingError::DuplicateContentLength
+        ));
+        assert!(matches!(
+            read("Content-Type: json\r\n\r\n{}"),
+            FramingError::MissingContentLength
+        ));
+        assert!(matches!(
+            read("Content-Length: 20\r\n\r\n{}"),
+            FramingError::UnexpectedEof
+        ));
+        assert!(matches!(
+            read("Content-Length: 2\r\n"),
+            FramingError::UnexpectedEof
+        ));
+        assert!(matches!(
+            read("Content-Length: 2\r\n\r\n{}"),
+            FramingError::Json(_)
+        ));
+    }
+
+    #[test]
+    fn reading_continues_after_invalid_headers() {
+        let mut bytes = b"Content-Length 2\r\nContent-Length: 2\r\n\r\n{}".to_vec();
+        bytes.extend_from_slice(b"Content-Length: 2\n\n{}");
+        bytes.extend_from_slice(
+            format!("X-Padding: {}\r\n", "x".repeat(MAX_HEADER_LENGTH)).as_bytes(),
+        );
+        bytes.extend_from_slice(b"Content-Length: 2\r\n\r\n{}");
+        let mut writer = MessageWriter::new(bytes);
+        writer.write(&request(1)).unwrap();
+        let mut reader = MessageReader::new(Trickle(writer.get_ref()));
+        assert!(matches!(
+            reader.read().unwrap_err(),
+            FramingError::MalformedHeader(_)
+        ));
+        assert!(matches!(
+            reader.read().unwrap_err(),
+            FramingError::MalformedHeader(_)
+        ));
+        assert!(matches!(
+            reader.read().unwrap_err(),
+            FramingError::HeaderTooLarge
+        ));
+        assert_eq!(reader.read().unwrap(), Some(request(1)));
+        assert!(reader.read().unwrap().is_none());
+    }
+
+    #[test]
+    fn oversized_messages() {
+        let mut reader = MessageReader::new("Content-Length: 1000\r\n\r\n".as_bytes())
+            .with_max_content_length(100);
+        assert!(matches!(
+            reader.read().unwrap_err(),
+            FramingError::ContentTooLarge {
+                length: 1000,
+                max: 100
+            }
+        ));
+
+        let header = format!("X-Padding: {}\r\n", "x".repeat(MAX_HEADER_LENGTH));
+        let mut reader = MessageReader::new(header.as_bytes());
+        assert!(matches!(
+            reader.read().unwrap_err(),
+            FramingError::HeaderTooLarge
+        ));
+    }
+}
@@ -3,18 +3,57 @@
 //! Based on: <https://microsoft.github.io/debug-adapter-protocol/specification>
 //! (generated from machine-readable schema).
 
+/// Debug adapter side of the protocol, with a handler method per request.
+pub mod adapter;
+/// Relation between adapter capabilities and the requests and argument fields they enable.
+pub mod capabilities;
+/// Async client for talking to a debug adapter.
+#[cfg(feature = "client")]
+pub mod client;
+/// Tokio codec for `Content-Length` framed protocol messages.
+#[cfg(feature = "tokio")]
+pub mod codec;
+/// Sequence number allocation and request/response correlation.
+pub mod correlator;
+/// Fan-out of events to subscribers registered per event type.
+pub mod dispatcher;
 /// Types representing events, with associated payload types.
 pub mod events;
+/// Typed access to vendor fields of protocol types.
+#[cfg(feature = "extra-fields")]
+pub mod extension;
+/// Reading and writing `Content-Length` framed protocol messages.
+pub mod framing;
+/// Protocol conformance checks for recorded message traces.
+pub mod lint;
 /// Types representing protocol messages.
 pub mod messages;
+/// Scriptable debug adapter for client tests.
+#[cfg(feature = "mock")]
+pub mod mock;
+/// Fake debug adapter that answers requests from a recorded trace.
+#[cfg(feature = "replay")]
+pub mod replay;
 /// Types representing requests, with associated argument and response types.
 pub mod requests;
+/// Runs a debug adapter over a transport.
+#[cfg(feature = "server")]
+pub mod server;
+/// Order of the initialize and configuration handshake.
+pub mod session;
+/// Recording format for protocol message traces.
+pub mod trace;
 mod types;
+/// Protocol versions that types are generated for, and types of older versions.
+pub mod versions;
 
 use std::cmp::Ordering;
 
+use serde::Deserialize;
+
 pub use crate::types::*;
 
+#[allow(clippy::derivable_impls)]
 impl Default for Capabilities {
     fn default() -> Self {
         Self {
@@ -60,6 +99,8 @@ impl Default for Capabilities {
             supports_value_formatting_options: None,
             supports_write_memory_request: None,
             supports_ansistyling: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
         }
     }
 }
@@ -183,6 +224,12 @@ impl Capabilities {
                 .breakpoint_modes
                 .or_else(|| self.breakpoint_modes.clone()),
             supports_ansistyling: other.supports_ansistyling.or(self.supports_ansistyling),
+            #[cfg(feature = "extra-fields")]
+            extra: {
+                let mut extra = self.extra.clone();
+                extra.extend(other.extra);
+                extra
+            },
         }
     }
 }
@@ -210,3 +257,13 @@ impl PartialOrd for Scope {
         Some(self.cmp(other))
     }
 }
+
+/// Deserializes a line number, reading the negative numbers that some adapters
+/// send for frames without a source as 0.
+pub(crate) fn negative_to_zero<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let line = i64::deserialize(deserializer)?;
+    Ok(line.max(0) as u64)
+}
@@ -0,0 +1,429 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::correlator::{CorrelationError, Correlator};
+use crate::events::check_event_body;
+use crate::messages::{decode_optional, Events, Message, Request, Response};
+use crate::requests::{self, check_response_body, Request as _};
+use crate::{ErrorResponse, ThreadEventReason};
+
+/// Requests that are sent by the adapter to the client.
+const REVERSE_REQUESTS: &[&str] = &[
+    requests::RunInTerminal::COMMAND,
+    requests::StartDebugging::COMMAND,
+];
+
+/// Requests that resume execution, after which variable references are no
+/// longer valid. Adapters don't send a `continued` event for these.
+const RESUMING_REQUESTS: &[&str] = &[
+    requests::Continue::COMMAND,
+    requests::Next::COMMAND,
+    requests::StepIn::COMMAND,
+    requests::StepOut::COMMAND,
+    requests::StepBack::COMMAND,
+    requests::ReverseContinue::COMMAND,
+    requests::Goto::COMMAND,
+    requests::RestartFrame::COMMAND,
+];
+
+/// Checks a recorded sequence of messages for protocol violations.
+///
+/// Messages sent by both the client and the adapter should be passed to
+/// [`Linter::check`] in the order they were sent. Which side sent a message is
+/// inferred from its kind: requests come from the client (except reverse
+/// requests such as `runInTerminal`), events come from the adapter.
+#[derive(Debug)]
+pub struct Linter {
+    index: usize,
+    client_requests: Correlator<()>,
+    adapter_requests: Correlator<()>,
+    last_client_seq: Option<u64>,
+    last_adapter_seq: Option<u64>,
+    /// Threads known from `threads` responses and `thread` events, or `None`
+    /// if the trace has not said anything about threads yet.
+    threads: Option<HashSet<i64>>,
+    /// Incremented whenever execution resumes.
+    generation: u64,
+    /// Generation in which each variable reference was handed out.
+    variables_references: HashMap<u64, u64>,
+}
+
+/// A protocol violation found by [`Linter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Position of the offending message in the trace, starting from 0.
+    pub index: usize,
+    pub rule: Rule,
+    pub message: String,
+}
+
+/// The checks performed by [`Linter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// Message is not a valid protocol message, or its arguments or body don't
+    /// fit the types of its command.
+    InvalidBody,
+    /// Response that doesn't answer a pending request with the same command.
+    UnmatchedResponse,
+    /// `seq` is not greater than the previous `seq` from the same side.
+    NonMonotonicSeq,
+    /// `stopped` event for a thread that is not known to exist.
+    UnknownThread,
+    /// Variable reference used after execution resumed.
+    StaleVariablesReference,
+}
+
+impl Linter {
+    pub fn new() -> Self {
+        Linter {
+            index: 0,
+            client_requests: Correlator::new(),
+            adapter_requests: Correlator::new(),
+            last_client_seq: None,
+            last_adapter_seq: None,
+            threads: None,
+            generation: 0,
+            variables_references: HashMap::new(),
+        }
+    }
+
+    /// Checks the next message of the trace.
+    pub fn check(&mut self, message: &Message) -> Vec<Violation> {
+        let mut found = Vec::new();
+        let mut report = |rule, message| found.push((rule, message));
+        match message {
+            Message::Request(request) => {
+                let from_adapter = REVERSE_REQUESTS.contains(&request.command.as_str());
+                self.check_seq(request.seq, from_adapter, &mut report);
+                self.check_request(request, from_adapter, &mut report);
+            }
+            Message::Response(response) => {
+                let from_adapter = !REVERSE_REQUESTS.contains(&response.command.as_str());
+                self.check_seq(response.seq, from_adapter, &mut report);
+                self.check_response(response, from_adapter, &mut report);
+            }
+            Message::Event(event) => {
+                self.check_seq(event.seq, true, &mut report);
+                self.check_event(&event.event, &mut report);
+            }
+        }
+        self.next_message(found)
+    }
+
+    /// Checks the next message of the trace, given as JSON.
+    pub fn check_value(&mut self, value: Value) -> Vec<Violation> {
+        let e = match Message::deserialize(&value) {
+            Ok(message) => return self.check(&message),
+            Err(e) => e,
+        };
+        let mut found = vec![(Rule::InvalidBody, format!("invalid message: {e}"))];
+        // The sequence number is still checked if the rest of the message is
+        // invalid.
+        let command = value["command"].as_str().unwrap_or_default();
+        let from_adapter = match value["type"].as_str() {
+            Some("event") => Some(true),
+            Some("request") => Some(REVERSE_REQUESTS.contains(&command)),
+            Some("response") => Some(!REVERSE_REQUESTS.contains(&command)),
+            _ => None,
+        };
+        if let (Some(seq), Some(from_adapter)) = (value["seq"].as_u64(), from_adapter) {
+            self.check_seq(seq, from_adapter, &mut |rule, message| {
+                found.push((rule, message))
+            });
+        }
+        self.next_message(found)
+    }
+
+    /// Moves on to the next message, returning the violations found in this one.
+    fn next_message(&mut self, found: Vec<(Rule, String)>) -> Vec<Violation> {
+        let index = self.index;
+        self.index += 1;
+        found
+            .into_iter()
+            .map(|(rule, message)| Violation {
+                index,
+                rule,
+                message,
+            })
+            .collect()
+    }
+
+    fn check_seq(&mut self, seq: u64, from_adapter: bool, report: &mut impl FnMut(Rule, String)) {
+        let (last, side) = if from_adapter {
+            (&mut self.last_adapter_seq, "adapter")
+        } else {
+            (&mut self.last_client_seq, "client")
+        };
+        if let Some(last) = *last {
+            if seq <= last {
+                report(
+                    Rule::NonMonotonicSeq,
+                    format!(
+                        "seq {seq} from the {side} is not greater than the previous seq {last}"
+                    ),
+                );
+            }
+        }
+        *last = Some(last.map_or(seq, |last| last.max(seq)));
+    }
+
+    fn check_request(
+        &mut self,
+        request: &Request,
+        from_adapter: bool,
+        report: &mut impl FnMut(Rule, String),
+    ) {
+        let correlator = if from_adapter {
+            &mut self.adapter_requests
+        } else {
+            &mut self.client_requests
+        };
+        // Reusing a seq is already reported as a non-monotonic seq.
+        let _ = correlator.insert(request, ());
+        if let Err(e) = request.typed() {
+            let command = &request.command;
+            report(
+                Rule::InvalidBody,
+                format!("invalid `{command}` arguments: {e}"),
+            );
+        }
+        let reference = request
+            .arguments
+            .as_ref()
+            .and_then(|a| a.get("variablesReference"))
+            .and_then(Value::as_u64);
+        if let Some(reference) = reference {
+            let issued = self.variables_references.get(&reference);
+            if issued.is_some_and(|&generation| generation < self.generation) {
+                report(
+                    Rule::StaleVariablesReference,
+                    format!(
+                        "`{}` uses variablesReference {reference} after execution resumed",
+                        request.command
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_response(
+        &mut self,
+        response: &Response,
+        from_adapter: bool,
+        report: &mut impl FnMut(Rule, String),
+    ) {
+        let correlator = if from_adapter {
+            &mut self.client_requests
+        } else {
+            &mut self.adapter_requests
+        };
+        match correlator.resolve(response) {
+            Ok(()) => {}
+            Err(e @ CorrelationError::CommandMismatch { .. }) => {
+                correlator.take(response.request_seq);
+                report(Rule::UnmatchedResponse, e.to_string());
+            }
+            Err(e) => report(Rule::UnmatchedResponse, e.to_string()),
+        }
+
+        let command = &response.command;
+        if !response.success {
+            if let Err(e) = decode_optional::<ErrorResponse>(response.body.clone()) {
+                report(
+                    Rule::InvalidBody,
+                    format!("invalid `{command}` error body: {e}"),
+                );
+            }
+            return;
+        }
+        if let Some(Err(e)) = check_response_body(command, response.body.clone()) {
+            report(
+                Rule::InvalidBody,
+                format!("invalid `{command}` response body: {e}"),
+            );
+        }
+        if RESUMING_REQUESTS.contains(&command.as_str()) {
+            self.generation += 1;
+        }
+        if command == requests::Threads::COMMAND {
+            if let Ok(body) = response.clone().into_typed::<requests::Threads>() {
+                self.threads = Some(body.threads.iter().map(|t| t.id).collect());
+            }
+        }
+        if let Some(body) = &response.body {
+            collect_variables_references(body, &mut |reference| {
+                self.variables_references.insert(reference, self.generation);
+            });
+        }
+    }
+
+    fn check_event(&mut self, event: &Events, report: &mut impl FnMut(Rule, String)) {
+        match event {
+            Events::Continued(_) => self.generation += 1,
+            Events::Thread(thread) => {
+                let threads = self.threads.get_or_insert_with(HashSet::new);
+                match thread.reason {
+                    ThreadEventReason::Started => {
+                        threads.insert(thread.thread_id);
+                    }
+                    ThreadEventReason::Exited => {
+                        threads.remove(&thread.thread_id);
+                    }
+                    _ => {}
+                }
+            }
+            Events::Stopped(stopped) => {
+                let (Some(threads), Some(id)) = (&self.threads, stopped.thread_id) else {
+                    return;
+                };
+                if !threads.contains(&id) {
+                    report(
+                        Rule::UnknownThread,
+                        format!("`stopped` event for unknown thread {id}"),
+                    );
+                }
+            }
+            // Events with bodies that don't fit their type are decoded as `Other`.
+            Events::Other(other) => {
+                if let Some(Err(e)) = check_event_body(&other.event, other.body.clone()) {
+                    let event = &other.event;
+                    report(Rule::InvalidBody, format!("invalid `{event}` body: {e}"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Checks a recorded sequence of messages, see [`Linter`].
+pub fn lint<'a>(messages: impl IntoIterator<Item = &'a Message>) -> Vec<Violation> {
+    let mut linter = Linter::new();
+    messages
+        .into_iter()
+        .flat_map(|message| linter.check(message))
+        .collect()
+}
+
+/// Finds every non-zero `variablesReference` in a response body.
+fn collect_variables_references(value: &Value, found: &mut impl FnMut(u64)) {
+    match value {
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                match value.as_u64() {
+                    Some(reference) if name == "variablesReference" && reference > 0 => {
+                        found(reference)
+                    }
+                    _ => collect_variables_references(value, found),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_variables_references(item, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "message {}: {:?}: {}",
+            self.index, self.rule, self.message
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn messages(values: Value) -> Vec<Message> {
+        serde_json::from_value(values).unwrap()
+    }
+
+    fn rules(violations: &[Violation]) -> Vec<(usize, Rule)> {
+        violations.iter().map(|v| (v.index, v.rule)).collect()
+    }
+
+    #[test]
+    fn accepts_valid_trace() {
+        let trace = messages(json!([
+            { "type": "request", "seq": 1, "command": "threads" },
+            { "type": "response", "seq": 1, "request_seq": 1, "success": true, "command": "threads",
+              "body": { "threads": [{ "id": 1, "name": "main" }] } },
+            { "type": "event", "seq": 2, "event": "stopped", "body": { "reason": "pause", "threadId": 1 } },
+            { "type": "request", "seq": 2, "command": "scopes", "arguments": { "frameId": 1 } },
+            { "type": "response", "seq": 3, "request_seq": 2, "success": true, "command": "scopes",
+              "body": { "scopes": [{ "name": "Locals", "variablesReference": 5, "expensive": false }] } },
+            { "type": "request", "seq": 3, "command": "variables", "arguments": { "variablesReference": 5 } },
+            { "type": "response", "seq": 4, "request_seq": 3, "success": false, "command": "variables",
+              "message": "cancelled" },
+        ]));
+        assert_eq!(lint(&trace), []);
+    }
+
+    #[test]
+    fn reports_violations() {
+        let trace = messages(json!([
+            { "type": "request", "seq": 2, "command": "threads" },
+            { "type": "request", "seq": 2, "command": "pause", "arguments": { "threadId": "main" } },
+            { "type": "response", "seq": 1, "request_seq": 7, "success": true, "command": "threads",
+              "body": { "threads": [] } },
+            { "type": "response", "seq": 2, "request_seq": 2, "success": true, "command": "threads",
+              "body": { "threads": [{ "id": 1, "name": "main" }] } },
+            { "type": "event", "seq": 3, "event": "stopped", "body": { "reason": "pause", "threadId": 2 } },
+            { "type": "response", "seq": 4, "request_seq": 9, "success": true, "command": "scopes",
+              "body": { "scopes": [{ "name": "Locals", "variablesReference": 5 }] } },
+            { "type": "event", "seq": 4, "event": "continued", "body": { "threadId": 1 } },
+            { "type": "request", "seq": 3, "command": "variables", "arguments": { "variablesReference": 5 } },
+        ]));
+        let violations = lint(&trace);
+        assert_eq!(
+            rules(&violations),
+            [
+                (1, Rule::NonMonotonicSeq),
+                (1, Rule::InvalidBody),
+                (2, Rule::UnmatchedResponse),
+                (4, Rule::UnknownThread),
+                (5, Rule::UnmatchedResponse),
+                (5, Rule::InvalidBody),
+                (6, Rule::NonMonotonicSeq),
+                (7, Rule::StaleVariablesReference),
+            ]
+        );
+        assert_eq!(
+            violations[3].to_string(),
+            "message 4: UnknownThread: `stopped` event for unknown thread 2"
+        );
+    }
+
+    #[test]
+    fn reports_invalid_messages() {
+        let mut linter = Linter::new();
+        let stopped =
+            json!({ "type": "event", "seq": 1, "event": "stopped", "body": { "threadId": 1 } });
+        assert_eq!(
+            rules(&linter.check_value(stopped)),
+            [(0, Rule::InvalidBody)]
+        );
+        let request = json!({ "type": "request", "seq": 1, "command": "threads" });
+        assert_eq!(linter.check_value(request), []);
+        let output = json!({ "type": "event", "seq": 1, "event": 5 });
+        assert_eq!(
+            rules(&linter.check_value(output)),
+            [(2, Rule::InvalidBody), (2, Rule::NonMonotonicSeq)]
+        );
+    }
+}
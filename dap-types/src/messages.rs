@@ -1,27 +1,131 @@
-use serde::{Deserialize, Deserializer, Serialize};
-use serde_json::Value;
+use serde::ser::SerializeMap;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
 
-use crate::{
-    BreakpointEvent, Capabilities, CapabilitiesEvent, ContinuedEvent, ExitedEvent,
-    InvalidatedEvent, LoadedSourceEvent, MemoryEvent, ModuleEvent, OutputEvent, ProcessEvent,
-    ProgressEndEvent, ProgressStartEvent, ProgressUpdateEvent, StoppedEvent, TerminatedEvent,
-    ThreadEvent,
-};
+pub use crate::events::Events;
+pub use crate::requests::Requests;
+use crate::{events, requests, ErrorResponse};
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum Message {
-    Event(Box<Events>),
+    Event(Box<Event>),
     Response(Response),
     Request(Request),
 }
 
+/// An event message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub seq: u64,
+    /// The event, with its body.
+    pub event: Events,
+    /// Fields of the message that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+impl Event {
+    pub fn new(seq: u64, event: Events) -> Self {
+        Event {
+            seq,
+            event,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        }
+    }
+}
+
+/// Event message as it is encoded, with the body not decoded yet.
+#[derive(Deserialize)]
+struct RawEvent {
+    seq: u64,
+    event: String,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    body: Option<Value>,
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    extra: serde_json::Map<String, Value>,
+}
+
+impl Serialize for Event {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("seq", &self.seq)?;
+        map.serialize_entry("event", self.event.name())?;
+        events::serialize_event_body(&self.event, &mut map)?;
+        #[cfg(feature = "extra-fields")]
+        for (key, value) in &self.extra {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawEvent::deserialize(deserializer)?;
+        let mut parts = serde_json::Map::new();
+        parts.insert("event".to_owned(), Value::String(raw.event));
+        if let Some(body) = raw.body {
+            parts.insert("body".to_owned(), body);
+        }
+        Ok(Event {
+            seq: raw.seq,
+            event: Events::deserialize(Value::Object(parts)).map_err(serde::de::Error::custom)?,
+            #[cfg(feature = "extra-fields")]
+            extra: raw.extra,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Request {
     pub seq: u64,
     pub command: String,
-    #[serde(default, deserialize_with = "deserialize_empty_object")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_present"
+    )]
     pub arguments: Option<Value>,
+    /// Fields of the message that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+impl Request {
+    /// Creates request `R`, taking the command from `R::COMMAND`.
+    pub fn new<R: requests::Request>(
+        seq: u64,
+        arguments: R::Arguments,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Request {
+            seq,
+            command: R::COMMAND.to_owned(),
+            arguments: encode_optional(arguments)?,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+    }
+
+    /// Creates a request from typed command and arguments.
+    pub fn from_typed(seq: u64, request: Requests) -> Result<Self, serde_json::Error> {
+        let (command, arguments) = request.into_parts()?;
+        Ok(Request {
+            seq,
+            command,
+            arguments,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+    }
+
+    /// Decodes the arguments according to the command.
+    pub fn typed(&self) -> Result<Requests, serde_json::Error> {
+        Requests::from_parts(self.command.clone(), self.arguments.clone())
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -30,78 +134,214 @@ pub struct Response {
     pub request_seq: u64,
     pub success: bool,
     pub command: String,
-    #[serde(default, deserialize_with = "deserialize_empty_object")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_present"
+    )]
     pub body: Option<Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    /// Fields of the message that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct OtherEvent {
     pub event: String,
+    #[serde(default)]
     pub body: Value,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
-#[serde(tag = "event", content = "body")]
-#[serde(rename_all = "camelCase")]
-pub enum Events {
-    Initialized(Option<Capabilities>),
-    Stopped(StoppedEvent),
-    Continued(ContinuedEvent),
-    Exited(ExitedEvent),
-    Terminated(Option<TerminatedEvent>),
-    Thread(ThreadEvent),
-    Output(OutputEvent),
-    Breakpoint(BreakpointEvent),
-    Module(ModuleEvent),
-    LoadedSource(LoadedSourceEvent),
-    Process(ProcessEvent),
-    Capabilities(CapabilitiesEvent),
-    ProgressStart(ProgressStartEvent),
-    ProgressUpdate(ProgressUpdateEvent),
-    ProgressEnd(ProgressEndEvent),
-    Invalidated(InvalidatedEvent),
-    Memory(MemoryEvent),
-    #[serde(untagged)]
-    Other(OtherEvent),
-}
-
-impl std::fmt::Display for Events {
+impl Response {
+    /// Creates a successful response to request `R`.
+    pub fn success<R: requests::Request>(
+        seq: u64,
+        request_seq: u64,
+        body: R::Response,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Response {
+            seq,
+            request_seq,
+            success: true,
+            command: R::COMMAND.to_owned(),
+            body: encode_optional(body)?,
+            message: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+    }
+
+    /// Creates an error response to a request with the given command.
+    ///
+    /// `message` is a short error reason (e.g. `cancelled`) or a free-form message,
+    /// `error` is an optional structured error for the body.
+    pub fn error(
+        seq: u64,
+        request_seq: u64,
+        command: impl Into<String>,
+        message: impl Into<String>,
+        error: Option<crate::Message>,
+    ) -> Self {
+        Response {
+            seq,
+            request_seq,
+            success: false,
+            command: command.into(),
+            body: error.map(|error| {
+                json!(ErrorResponse {
+                    error: Some(error),
+                    #[cfg(feature = "extra-fields")]
+                    extra: Default::default(),
+                })
+            }),
+            message: Some(message.into()),
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        }
+    }
+
+    /// Decodes the body of a response to request `R`.
+    ///
+    /// Fails if the response is for a different command, if the request did not
+    /// succeed, or if the body does not match `R::Response`.
+    pub fn into_typed<R: requests::Request>(self) -> Result<R::Response, ResponseError> {
+        if self.command != R::COMMAND {
+            return Err(ResponseError::CommandMismatch {
+                expected: R::COMMAND,
+                actual: self.command,
+            });
+        }
+        if !self.success {
+            let body = decode_optional(self.body).unwrap_or(ErrorResponse {
+                error: None,
+                #[cfg(feature = "extra-fields")]
+                extra: Default::default(),
+            });
+            return Err(ResponseError::Failed {
+                message: self.message,
+                body: Box::new(body),
+            });
+        }
+        decode_optional(self.body).map_err(ResponseError::Body)
+    }
+}
+
+/// Error returned when decoding a typed response body.
+#[derive(Debug)]
+pub enum ResponseError {
+    /// Response is for a different command than the one expected.
+    CommandMismatch {
+        expected: &'static str,
+        actual: String,
+    },
+    /// Response has `success: false`.
+    Failed {
+        /// Short error reason, e.g. `cancelled` or `notStopped`, or a free-form message.
+        message: Option<String>,
+        body: Box<ErrorResponse>,
+    },
+    /// Response body does not match the response type of the request.
+    Body(serde_json::Error),
+}
+
+impl std::fmt::Display for ResponseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Events::Initialized(_) => write!(f, "Initialized"),
-            Events::Stopped(_) => write!(f, "Stopped"),
-            Events::Continued(_) => write!(f, "Continued"),
-            Events::Exited(_) => write!(f, "Exited"),
-            Events::Terminated(_) => write!(f, "Terminated"),
-            Events::Thread(_) => write!(f, "Thread"),
-            Events::Output(_) => write!(f, "Output"),
-            Events::Breakpoint(_) => write!(f, "Breakpoint"),
-            Events::Module(_) => write!(f, "Module"),
-            Events::LoadedSource(_) => write!(f, "LoadedSource"),
-            Events::Process(_) => write!(f, "Process"),
-            Events::Capabilities(_) => write!(f, "Capabilities"),
-            Events::ProgressStart(_) => write!(f, "ProgressStart"),
-            Events::ProgressUpdate(_) => write!(f, "ProgressUpdate"),
-            Events::ProgressEnd(_) => write!(f, "ProgressEnd"),
-            Events::Invalidated(_) => write!(f, "Invalidated"),
-            Events::Memory(_) => write!(f, "Memory"),
-            Events::Other(other) => write!(f, "{}", other.event.as_str()),
+            ResponseError::CommandMismatch { expected, actual } => {
+                write!(f, "expected response to {expected:?}, got {actual:?}")
+            }
+            ResponseError::Failed { message, body } => {
+                write!(f, "request failed")?;
+                if let Some(message) = message {
+                    write!(f, ": {message}")?;
+                }
+                if let Some(error) = &body.error {
+                    write!(f, " ({})", error.format)?;
+                }
+                Ok(())
+            }
+            ResponseError::Body(e) => write!(f, "invalid response body: {e}"),
         }
     }
 }
 
-fn deserialize_empty_object<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
+impl std::error::Error for ResponseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ResponseError::Body(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OtherRequest {
+    pub command: String,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_present"
+    )]
+    pub arguments: Option<Value>,
+}
+
+/// A request with borrowed command and arguments, encoded like [`OtherRequest`].
+#[derive(Serialize)]
+pub(crate) struct RequestParts<'a, T> {
+    pub command: &'a str,
+    pub arguments: &'a T,
+}
+
+impl<'de> Deserialize<'de> for Requests {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = OtherRequest::deserialize(deserializer)?;
+        Requests::from_parts(raw.command, raw.arguments).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Events {
+    /// Creates event `E` with the given body.
+    pub fn new<E: events::Event>(body: E::Body) -> Self {
+        E::into_events(body)
+    }
+
+    /// Returns the body of the event if it is event `E`.
+    ///
+    /// Events that were decoded as `Other` are never downcast, even if their name
+    /// is `E::EVENT`.
+    pub fn downcast<E: events::Event>(&self) -> Option<&E::Body> {
+        E::from_events(self)
+    }
+}
+
+/// Encodes a payload, using `None` for payloads that encode as `null`.
+fn encode_optional<T: Serialize>(value: T) -> Result<Option<Value>, serde_json::Error> {
+    Ok(Some(serde_json::to_value(value)?).filter(|v| !v.is_null()))
+}
+
+/// Decodes an optional payload. A missing, `null` or empty payload is decoded
+/// from `null`, or from an empty object for types that can't be decoded from
+/// `null`.
+pub(crate) fn decode_optional<T: DeserializeOwned>(
+    value: Option<Value>,
+) -> Result<T, serde_json::Error> {
+    let empty = Value::Object(serde_json::Map::new());
+    match value {
+        Some(value) if !value.is_null() && value != empty => serde_json::from_value(value),
+        _ => serde_json::from_value(Value::Null).or_else(|_| serde_json::from_value(empty)),
+    }
+}
+
+/// Deserializes a payload that is present, keeping `null` and empty objects so
+/// that the message is encoded the same way again.
+fn deserialize_present<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let value = Value::deserialize(deserializer)?;
-    if value == Value::Object(serde_json::Map::new()) {
-        Ok(None)
-    } else {
-        Ok(Some(value))
-    }
+    Value::deserialize(deserializer).map(Some)
 }
 
 #[cfg(test)]
@@ -121,6 +361,21 @@ mod tests {
         });
         let stopped_event: Events = serde_json::from_value(stopped_json).unwrap();
         assert!(matches!(stopped_event, Events::Stopped(_)));
+        assert_eq!(stopped_event.name(), "stopped");
+
+        // `terminated` has an optional body
+        let terminated: Events = serde_json::from_value(json!({ "event": "terminated" })).unwrap();
+        assert_eq!(terminated, Events::Terminated(None));
+        assert_eq!(Events::new::<events::Terminated>(None), terminated);
+
+        // Percentages are fractional
+        let progress = json!({
+            "event": "progressUpdate",
+            "body": { "progressId": "1", "percentage": 42.5 }
+        });
+        let progress: Events = serde_json::from_value(progress).unwrap();
+        let body = progress.downcast::<events::ProgressUpdate>().unwrap();
+        assert_eq!(body.percentage, Some(42.5));
 
         // Test an unknown event type
         let unknown_json = json!({
@@ -131,6 +386,7 @@ mod tests {
             }
         });
         let unknown_event: Events = serde_json::from_value(unknown_json).unwrap();
+        assert_eq!(unknown_event.name(), "customEvent");
 
         if let Events::Other(other) = unknown_event {
             assert_eq!(other.event, "customEvent");
@@ -145,4 +401,268 @@ mod tests {
             panic!("Expected Other variant for unknown event");
         }
     }
+
+    #[test]
+    fn test_requests_deserialization() {
+        let request: Request = serde_json::from_value(json!({
+            "seq": 1,
+            "type": "request",
+            "command": "setBreakpoints",
+            "arguments": {
+                "source": { "path": "main.rs" },
+                "breakpoints": [{ "line": 10 }]
+            }
+        }))
+        .unwrap();
+        let Requests::SetBreakpoints(args) = request.typed().unwrap() else {
+            panic!("Expected SetBreakpoints variant");
+        };
+        assert_eq!(args.source.path.as_deref(), Some("main.rs"));
+        assert_eq!(args.breakpoints.unwrap()[0].line, 10);
+
+        // Arguments are optional for some requests.
+        let requests: Vec<Requests> = serde_json::from_value(json!([
+            { "command": "configurationDone" },
+            { "command": "threads", "arguments": {} },
+        ]))
+        .unwrap();
+        assert!(matches!(requests[0], Requests::ConfigurationDone(_)));
+        assert!(matches!(requests[1], Requests::Threads(_)));
+
+        let custom = json!({
+            "command": "customCommand",
+            "arguments": { "someField": "someValue" }
+        });
+        let request: Requests = serde_json::from_value(custom.clone()).unwrap();
+        assert_eq!(request.command(), "customCommand");
+        assert_eq!(serde_json::to_value(&request).unwrap(), custom);
+
+        let bad = json!({ "command": "pause", "arguments": { "threadId": "main" } });
+        assert!(serde_json::from_value::<Requests>(bad).is_err());
+    }
+
+    #[test]
+    fn test_typed_constructors() {
+        let arguments = crate::PauseArguments {
+            thread_id: 3,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        };
+        let request = Request::new::<requests::Pause>(1, arguments).unwrap();
+        assert_eq!(request.command, "pause");
+        assert_eq!(request.arguments, Some(json!({ "threadId": 3 })));
+        assert!(matches!(
+            request.typed().unwrap(),
+            Requests::Pause(crate::PauseArguments { thread_id: 3, .. })
+        ));
+        let typed = Request::from_typed(1, request.typed().unwrap()).unwrap();
+        assert_eq!(typed, request);
+
+        let response = Response::success::<requests::Next>(2, 1, ()).unwrap();
+        assert_eq!(response.command, "next");
+        assert_eq!(response.body, None);
+        response.into_typed::<requests::Next>().unwrap();
+
+        let error = crate::Message {
+            id: 1,
+            format: "no such thread".to_owned(),
+            variables: None,
+            send_telemetry: None,
+            show_user: Some(true),
+            url: None,
+            url_label: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        };
+        let response = Response::error(3, 1, "pause", "invalid thread", Some(error));
+        let Err(ResponseError::Failed { message, body }) = response.into_typed::<requests::Pause>()
+        else {
+            panic!("Expected failed response");
+        };
+        assert_eq!(message.as_deref(), Some("invalid thread"));
+        assert_eq!(body.error.unwrap().format, "no such thread");
+
+        let stopped = Events::new::<events::Stopped>(crate::StoppedEvent {
+            reason: crate::StoppedEventReason::Breakpoint,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        });
+        assert!(matches!(stopped, Events::Stopped(_)));
+        let stopped_body = stopped.downcast::<events::Stopped>().unwrap();
+        assert_eq!(stopped_body.thread_id, Some(1));
+        assert_eq!(stopped.downcast::<events::Continued>(), None);
+        let initialized = Events::new::<events::Initialized>(None);
+        assert_eq!(initialized, Events::Initialized(None));
+        assert_eq!(initialized.downcast::<events::Initialized>(), Some(&None));
+    }
+
+    #[test]
+    fn test_events_without_body() {
+        let initialized = json!({ "seq": 1, "type": "event", "event": "initialized" });
+        let message = serde_json::from_value::<Message>(initialized.clone()).unwrap();
+        assert!(matches!(&message, Message::Event(e) if e.event == Events::Initialized(None)));
+        assert_eq!(serde_json::to_value(&message).unwrap(), initialized);
+
+        // Some adapters send their capabilities with `initialized`.
+        for body in [
+            json!({}),
+            json!({ "supportsConfigurationDoneRequest": true }),
+        ] {
+            let initialized =
+                json!({ "seq": 1, "type": "event", "event": "initialized", "body": body });
+            let message = serde_json::from_value::<Message>(initialized.clone()).unwrap();
+            assert!(
+                matches!(&message, Message::Event(e) if matches!(e.event, Events::Initialized(Some(_))))
+            );
+            assert_eq!(serde_json::to_value(&message).unwrap(), initialized);
+        }
+
+        let other = json!({ "seq": 2, "type": "event", "event": "vendorEvent" });
+        let message = serde_json::from_value::<Message>(other.clone()).unwrap();
+        assert!(matches!(&message, Message::Event(e) if e.event.name() == "vendorEvent"));
+        assert_eq!(serde_json::to_value(&message).unwrap(), other);
+    }
+
+    #[test]
+    fn test_typed_responses() {
+        let response = |value| serde_json::from_value::<Response>(value).unwrap();
+
+        let threads = response(json!({
+            "seq": 2,
+            "type": "response",
+            "request_seq": 1,
+            "success": true,
+            "command": "threads",
+            "body": { "threads": [{ "id": 1, "name": "main" }] }
+        }));
+        let body = threads.clone().into_typed::<requests::Threads>().unwrap();
+        assert_eq!(body.threads[0].name, "main");
+        assert!(matches!(
+            threads.into_typed::<requests::Scopes>(),
+            Err(ResponseError::CommandMismatch {
+                expected: "scopes",
+                ..
+            })
+        ));
+
+        let initialize = response(json!({
+            "seq": 2,
+            "type": "response",
+            "request_seq": 1,
+            "success": true,
+            "command": "initialize",
+        }));
+        let capabilities = initialize.into_typed::<requests::Initialize>().unwrap();
+        assert_eq!(capabilities, crate::Capabilities::default());
+
+        let next = response(json!({
+            "seq": 2,
+            "type": "response",
+            "request_seq": 1,
+            "success": true,
+            "command": "next",
+            "body": {}
+        }));
+        next.into_typed::<requests::Next>().unwrap();
+
+        let failed = response(json!({
+            "seq": 2,
+            "type": "response",
+            "request_seq": 1,
+            "success": false,
+            "command": "evaluate",
+            "message": "notStopped",
+            "body": { "error": { "id": 7, "format": "not stopped" } }
+        }));
+        let Err(ResponseError::Failed { message, body }) =
+            failed.into_typed::<requests::Evaluate>()
+        else {
+            panic!("Expected failed response");
+        };
+        assert_eq!(message.as_deref(), Some("notStopped"));
+        assert_eq!(body.error.unwrap().id, 7);
+
+        let malformed = response(json!({
+            "seq": 2,
+            "type": "response",
+            "request_seq": 1,
+            "success": true,
+            "command": "evaluate",
+            "body": { "result": 42 }
+        }));
+        assert!(matches!(
+            malformed.into_typed::<requests::Evaluate>(),
+            Err(ResponseError::Body(_))
+        ));
+    }
+
+    #[cfg(not(feature = "extra-fields"))]
+    #[test]
+    fn test_unknown_enum_values() {
+        let stopped = json!({ "reason": "vendor stop", "threadId": 1 });
+        let body: crate::StoppedEvent = serde_json::from_value(stopped).unwrap();
+        assert_eq!(body.reason, crate::StoppedEventReason::Unknown);
+    }
+
+    #[cfg(feature = "extra-fields")]
+    #[test]
+    fn test_vendor_fields_round_trip() {
+        let output = json!({
+            "seq": 5,
+            "type": "event",
+            "event": "output",
+            "body": { "output": "hi\n", "category": "stdout", "vendorTimestamp": 17 },
+            "vendorSession": "a"
+        });
+        let message: Message = serde_json::from_value(output.clone()).unwrap();
+        let Message::Event(event) = &message else {
+            panic!("Expected event");
+        };
+        assert_eq!(event.seq, 5);
+        assert_eq!(event.extra["vendorSession"], "a");
+        let body = event.event.downcast::<events::Output>().unwrap();
+        assert_eq!(body.extra["vendorTimestamp"], 17);
+        assert_eq!(serde_json::to_value(&message).unwrap(), output);
+
+        for message in [
+            json!({ "seq": 1, "type": "event", "event": "initialized" }),
+            json!({ "seq": 2, "type": "request", "command": "threads", "vendorTrace": true }),
+            json!({ "seq": 3, "type": "request", "command": "threads", "arguments": {} }),
+            json!({
+                "seq": 4,
+                "type": "response",
+                "request_seq": 2,
+                "success": true,
+                "command": "threads",
+                "vendorTime": 3
+            }),
+        ] {
+            let decoded: Message = serde_json::from_value(message.clone()).unwrap();
+            assert_eq!(serde_json::to_value(&decoded).unwrap(), message);
+        }
+
+        // Values of open enums that the protocol doesn't define are kept.
+        let stopped = json!({ "reason": "vendor stop", "threadId": 1 });
+        let body: crate::StoppedEvent = serde_json::from_value(stopped.clone()).unwrap();
+        assert_eq!(
+            body.reason,
+            crate::StoppedEventReason::Other("vendor stop".to_owned())
+        );
+        assert_eq!(serde_json::to_value(&body).unwrap(), stopped);
+
+        let frames = json!({
+            "stackFrames": [
+                { "id": 1, "name": "main", "line": 3, "column": 1, "vendorModule": "a.out" }
+            ],
+        });
+        let body: crate::StackTraceResponse = serde_json::from_value(frames.clone()).unwrap();
+        assert_eq!(body.stack_frames[0].extra["vendorModule"], "a.out");
+        assert_eq!(serde_json::to_value(&body).unwrap(), frames);
+    }
 }
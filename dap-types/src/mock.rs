@@ -0,0 +1,381 @@
+use std::sync::{Arc, Mutex};
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::DuplexStream;
+use tokio::sync::mpsc;
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+use crate::codec::MessageCodec;
+use crate::events;
+use crate::framing::FramingError;
+use crate::messages::{decode_optional, Event, Events, Message, Request, Response};
+use crate::requests;
+
+/// Debug adapter for client tests, answering requests as programmed by the test.
+///
+/// Each rule answers requests of one type, optionally only those whose
+/// arguments match a predicate, and can be followed by events:
+///
+/// - [`MockAdapter::on`] answers every `R` request with the same response,
+/// - [`MockAdapter::on_when`] answers `R` requests whose arguments match,
+/// - [`MockAdapter::fail`] answers `R` requests with an error,
+/// - [`MockAdapter::handle`] computes the response from the arguments.
+///
+/// When several rules match a request, the one added last is used. Requests
+/// that no rule matches get an "unsupported request" error response.
+///
+/// [`MockAdapter::start`] runs the adapter on a background task and returns the
+/// client end of an in-memory pipe.
+pub struct MockAdapter {
+    rules: Vec<Rule>,
+}
+
+/// A programmed answer to requests, returned by the [`MockAdapter`] methods
+/// that add rules.
+pub struct Rule {
+    command: &'static str,
+    respond: Box<Respond>,
+    events: Vec<Events>,
+}
+
+type Respond = dyn FnMut(&Request) -> Option<Response> + Send;
+
+/// Handle for a running [`MockAdapter`].
+#[derive(Debug, Clone)]
+pub struct MockHandle {
+    outgoing: mpsc::UnboundedSender<Message>,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockAdapter {
+    pub fn new() -> Self {
+        MockAdapter { rules: Vec::new() }
+    }
+
+    /// Answers every request `R` with `response`.
+    pub fn on<R: requests::Request + 'static>(&mut self, response: R::Response) -> &mut Rule {
+        self.on_when::<R>(|_| true, response)
+    }
+
+    /// Answers requests `R` whose arguments match `matches` with `response`.
+    pub fn on_when<R: requests::Request + 'static>(
+        &mut self,
+        matches: impl Fn(&R::Arguments) -> bool + Send + 'static,
+        response: R::Response,
+    ) -> &mut Rule {
+        self.add::<R>(move |arguments| matches(arguments).then(|| Ok(response.clone())))
+    }
+
+    /// Answers every request `R` with an error response with `message`.
+    pub fn fail<R: requests::Request + 'static>(
+        &mut self,
+        message: impl Into<String>,
+    ) -> &mut Rule {
+        let message = message.into();
+        self.add::<R>(move |_| Some(Err(message.clone())))
+    }
+
+    /// Answers every request `R` with the result of `handler`, where an error
+    /// is sent as an error response with that message.
+    pub fn handle<R: requests::Request + 'static>(
+        &mut self,
+        mut handler: impl FnMut(R::Arguments) -> Result<R::Response, String> + Send + 'static,
+    ) -> &mut Rule {
+        self.add::<R>(move |arguments| Some(handler(arguments.clone())))
+    }
+
+    fn add<R: requests::Request + 'static>(
+        &mut self,
+        mut respond: impl FnMut(&R::Arguments) -> Option<Result<R::Response, String>> + Send + 'static,
+    ) -> &mut Rule {
+        let respond = move |request: &Request| {
+            // Requests with arguments that don't decode don't match the rule.
+            let arguments = decode_optional::<R::Arguments>(request.arguments.clone()).ok()?;
+            let response = respond(&arguments)?.and_then(|body| {
+                Response::success::<R>(0, request.seq, body).map_err(|e| e.to_string())
+            });
+            Some(response.unwrap_or_else(|message| {
+                Response::error(0, request.seq, R::COMMAND, message, None)
+            }))
+        };
+        self.rules.push(Rule {
+            command: R::COMMAND,
+            respond: Box::new(respond),
+            events: Vec::new(),
+        });
+        self.rules.last_mut().unwrap()
+    }
+
+    /// Starts answering requests on a background task, and returns the client
+    /// end of the connection.
+    ///
+    /// This must be called from within a tokio runtime.
+    pub fn start(self) -> (DuplexStream, MockHandle) {
+        let (client, adapter) = tokio::io::duplex(4096);
+        let (outgoing, outgoing_rx) = mpsc::unbounded_channel();
+        let handle = MockHandle {
+            outgoing,
+            requests: Arc::new(Mutex::new(Vec::new())),
+        };
+        tokio::spawn(self.run(adapter, outgoing_rx, handle.requests.clone()));
+        (client, handle)
+    }
+
+    async fn run(
+        mut self,
+        stream: DuplexStream,
+        mut outgoing: mpsc::UnboundedReceiver<Message>,
+        requests: Arc<Mutex<Vec<Request>>>,
+    ) -> Result<(), FramingError> {
+        let (reader, writer) = tokio::io::split(stream);
+        let mut reader = FramedRead::new(reader, MessageCodec::new());
+        let mut writer = FramedWrite::new(writer, MessageCodec::new());
+        let mut next_seq = 1;
+        loop {
+            let messages = tokio::select! {
+                message = reader.next() => match message {
+                    Some(Ok(Message::Request(request))) => {
+                        requests.lock().unwrap().push(request.clone());
+                        self.answer(&request)
+                    }
+                    // Responses to reverse requests and unknown messages are ignored.
+                    Some(Ok(_)) | Some(Err(FramingError::Json(_))) => continue,
+                    Some(Err(e)) => return Err(e),
+                    None => return Ok(()),
+                },
+                Some(message) = outgoing.recv() => vec![message],
+            };
+            for mut message in messages {
+                match &mut message {
+                    Message::Request(request) => request.seq = next_seq,
+                    Message::Response(response) => response.seq = next_seq,
+                    Message::Event(event) => event.seq = next_seq,
+                }
+                next_seq += 1;
+                writer.send(message).await?;
+            }
+        }
+    }
+
+    fn answer(&mut self, request: &Request) -> Vec<Message> {
+        for rule in self.rules.iter_mut().rev() {
+            if rule.command != request.command {
+                continue;
+            }
+            let Some(response) = (rule.respond)(request) else {
+                continue;
+            };
+            let events = rule.events.iter().cloned();
+            return std::iter::once(Message::Response(response))
+                .chain(events.map(|event| Message::Event(Box::new(Event::new(0, event)))))
+                .collect();
+        }
+        let message = format!("unsupported request: {}", request.command);
+        let response = Response::error(0, request.seq, &request.command, message, None);
+        vec![Message::Response(response)]
+    }
+}
+
+impl Default for MockAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule {
+    /// Sends event `E` after each response given by this rule.
+    pub fn then<E: events::Event>(&mut self, body: E::Body) -> &mut Self {
+        self.events.push(Events::new::<E>(body));
+        self
+    }
+}
+
+impl MockHandle {
+    /// Sends event `E` to the client.
+    ///
+    /// Returns false if the adapter is no longer running.
+    pub fn event<E: events::Event>(&self, body: E::Body) -> bool {
+        let event = Event::new(0, Events::new::<E>(body));
+        self.outgoing.send(Message::Event(Box::new(event))).is_ok()
+    }
+
+    /// Requests received so far, in the order they arrived.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::requests::Request as _;
+    use serde_json::json;
+
+    struct Connection {
+        reader: FramedRead<tokio::io::ReadHalf<DuplexStream>, MessageCodec>,
+        writer: FramedWrite<tokio::io::WriteHalf<DuplexStream>, MessageCodec>,
+        seq: u64,
+    }
+
+    impl Connection {
+        fn new(stream: DuplexStream) -> Self {
+            let (reader, writer) = tokio::io::split(stream);
+            Connection {
+                reader: FramedRead::new(reader, MessageCodec::new()),
+                writer: FramedWrite::new(writer, MessageCodec::new()),
+                seq: 0,
+            }
+        }
+
+        async fn send<R: requests::Request>(&mut self, arguments: R::Arguments) -> Response {
+            self.seq += 1;
+            let request = Request::new::<R>(self.seq, arguments).unwrap();
+            self.writer.send(Message::Request(request)).await.unwrap();
+            match self.next().await {
+                Message::Response(response) => response,
+                other => panic!("unexpected message {other:?}"),
+            }
+        }
+
+        async fn next(&mut self) -> Message {
+            self.reader.next().await.unwrap().unwrap()
+        }
+    }
+
+    fn stack_trace(thread_id: i64) -> crate::StackTraceArguments {
+        serde_json::from_value(json!({ "threadId": thread_id })).unwrap()
+    }
+
+    #[tokio::test]
+    async fn answers_programmed_requests() {
+        let frames = serde_json::from_value::<crate::StackTraceResponse>(json!({
+            "stackFrames": [{ "id": 1, "name": "main", "line": 3, "column": 1 }],
+        }));
+        let stopped = serde_json::from_value::<crate::StoppedEvent>(json!({
+            "reason": "breakpoint",
+            "threadId": 1,
+        }));
+        let mut mock = MockAdapter::new();
+        mock.on_when::<requests::StackTrace>(|args| args.thread_id == 1, frames.unwrap());
+        mock.on::<requests::Continue>(crate::ContinueResponse {
+            all_threads_continued: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+        .then::<events::Stopped>(stopped.unwrap());
+        let (stream, handle) = mock.start();
+        let mut client = Connection::new(stream);
+
+        let response = client.send::<requests::StackTrace>(stack_trace(1)).await;
+        let frames = response.into_typed::<requests::StackTrace>().unwrap();
+        assert_eq!(frames.stack_frames[0].name, "main");
+        let response = client.send::<requests::StackTrace>(stack_trace(2)).await;
+        assert_eq!(
+            response.message.as_deref(),
+            Some("unsupported request: stackTrace")
+        );
+
+        let arguments = serde_json::from_value(json!({ "threadId": 1 })).unwrap();
+        let response = client.send::<requests::Continue>(arguments).await;
+        assert!(response.success);
+        assert!(matches!(
+            client.next().await,
+            Message::Event(event) if matches!(
+                &event.event,
+                Events::Stopped(e) if e.reason == crate::StoppedEventReason::Breakpoint
+            )
+        ));
+
+        let commands = handle.requests().into_iter().map(|r| r.command);
+        assert_eq!(
+            commands.collect::<Vec<_>>(),
+            ["stackTrace", "stackTrace", "continue"]
+        );
+    }
+
+    #[tokio::test]
+    async fn later_rules_take_precedence() {
+        let mut mock = MockAdapter::new();
+        mock.handle::<requests::StackTrace>(|_| Err("not reached".to_owned()));
+        mock.fail::<requests::Threads>("not yet");
+        mock.fail::<requests::StackTrace>("no such thread");
+        mock.on_when::<requests::StackTrace>(
+            |args| args.thread_id == 0,
+            crate::StackTraceResponse {
+                stack_frames: Vec::new(),
+                total_frames: None,
+                #[cfg(feature = "extra-fields")]
+                extra: Default::default(),
+            },
+        );
+        let (stream, handle) = mock.start();
+        let mut client = Connection::new(stream);
+
+        let response = client.send::<requests::StackTrace>(stack_trace(3)).await;
+        assert_eq!(response.message.as_deref(), Some("no such thread"));
+        let response = client.send::<requests::StackTrace>(stack_trace(0)).await;
+        assert!(response.success);
+        let response = client
+            .send::<requests::Threads>(crate::ThreadsArgument::default())
+            .await;
+        assert_eq!(
+            (response.command.as_str(), response.success),
+            (requests::Threads::COMMAND, false)
+        );
+
+        let exited = crate::ExitedEvent {
+            exit_code: 0,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        };
+        assert!(handle.event::<events::Exited>(exited));
+        assert!(matches!(
+            client.next().await,
+            Message::Event(event) if matches!(event.event, Events::Exited(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn handles_requests_with_their_arguments() {
+        let mut mock = MockAdapter::new();
+        let mut calls = 0;
+        mock.handle::<requests::StackTrace>(move |args| {
+            calls += 1;
+            if args.thread_id == 0 {
+                return Err("no such thread".to_owned());
+            }
+            Ok(crate::StackTraceResponse {
+                stack_frames: Vec::new(),
+                total_frames: Some(calls * 10 + args.thread_id as u64),
+                #[cfg(feature = "extra-fields")]
+                extra: Default::default(),
+            })
+        });
+        let (stream, _handle) = mock.start();
+        let mut client = Connection::new(stream);
+
+        let response = client.send::<requests::StackTrace>(stack_trace(3)).await;
+        let frames = response.into_typed::<requests::StackTrace>().unwrap();
+        assert_eq!(frames.total_frames, Some(13));
+        let response = client.send::<requests::StackTrace>(stack_trace(0)).await;
+        assert_eq!(response.message.as_deref(), Some("no such thread"));
+        let response = client.send::<requests::StackTrace>(stack_trace(4)).await;
+        let frames = response.into_typed::<requests::StackTrace>().unwrap();
+        assert_eq!(frames.total_frames, Some(34));
+    }
+
+    #[test]
+    fn responses_without_body_have_no_body() {
+        let mut mock = MockAdapter::new();
+        mock.handle::<requests::Cancel>(|_| Ok(()));
+        let arguments = serde_json::from_value(json!({})).unwrap();
+        let request = Request::new::<requests::Cancel>(1, arguments).unwrap();
+        match mock.answer(&request).as_slice() {
+            [Message::Response(response)] => {
+                assert!(response.success);
+                assert_eq!(response.body, None);
+            }
+            other => panic!("unexpected messages {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+use crate::codec::MessageCodec;
+use crate::framing::FramingError;
+use crate::messages::{Message, Request, Response};
+use crate::requests::{self, Request as _};
+use crate::trace::{Direction, TraceEntry};
+
+/// Fake debug adapter that answers requests from a recorded trace.
+///
+/// Requests are matched to recorded requests by `command` and `arguments`, so
+/// the client does not need to number its requests the same way as when the
+/// trace was recorded. A match is answered with the recorded response and with
+/// the events and reverse requests the adapter sent after it, in the recorded
+/// order. Messages the adapter sent before the first request are written as
+/// soon as the replay starts. Recorded messages that are not valid protocol
+/// messages are skipped.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    initial: Vec<Message>,
+    exchanges: Vec<Exchange>,
+}
+
+#[derive(Debug, Clone)]
+struct Exchange {
+    seq: u64,
+    command: String,
+    arguments: Option<Value>,
+    messages: Vec<Message>,
+    used: bool,
+}
+
+impl Replay {
+    pub fn new(entries: impl IntoIterator<Item = TraceEntry>) -> Self {
+        let mut initial = Vec::new();
+        let mut exchanges = Vec::<Exchange>::new();
+        // Exchange that adapter messages are added to: the last request, or the
+        // request that the adapter last responded to, whichever came later.
+        let mut current = None;
+        for entry in entries {
+            let message = match entry.decode() {
+                Ok(message) => message,
+                Err(e) => {
+                    log::warn!("skipping invalid message in trace: {e}");
+                    continue;
+                }
+            };
+            match (entry.direction, message) {
+                (Direction::ClientToAdapter, Message::Request(request)) => {
+                    current = Some(exchanges.len());
+                    exchanges.push(Exchange {
+                        seq: request.seq,
+                        command: request.command,
+                        arguments: request.arguments,
+                        messages: Vec::new(),
+                        used: false,
+                    });
+                }
+                // Responses to reverse requests are not replayed.
+                (Direction::ClientToAdapter, _) => {}
+                (Direction::AdapterToClient, message) => {
+                    // Responses can be sent after the client has made another
+                    // request, so they go with the request they answer.
+                    if let Message::Response(response) = &message {
+                        current = exchanges
+                            .iter()
+                            .rposition(|e| e.seq == response.request_seq)
+                            .or(current);
+                    }
+                    match current {
+                        Some(i) => exchanges[i].messages.push(message),
+                        None => initial.push(message),
+                    }
+                }
+            }
+        }
+        Replay { initial, exchanges }
+    }
+
+    /// Returns the recorded messages answering `request`, or `None` if no
+    /// recorded request has the same command and arguments.
+    ///
+    /// Each recorded request is used once, in the recorded order. When all of
+    /// the matching ones have been used, the last one is used again. The
+    /// response is addressed to `request`, its `seq` is left as recorded.
+    pub fn respond(&mut self, request: &Request) -> Option<Vec<Message>> {
+        let arguments = non_empty(&request.arguments);
+        let matches = |e: &&mut Exchange| {
+            e.command == request.command && non_empty(&e.arguments) == arguments
+        };
+        let exchange = match self.exchanges.iter_mut().filter(matches).find(|e| !e.used) {
+            Some(exchange) => exchange,
+            None => self.exchanges.iter_mut().rfind(matches)?,
+        };
+        exchange.used = true;
+        let mut messages = exchange.messages.clone();
+        for message in &mut messages {
+            if let Message::Response(response) = message {
+                if response.request_seq == exchange.seq {
+                    response.request_seq = request.seq;
+                }
+            }
+        }
+        Some(messages)
+    }
+
+    /// Answers requests until the client disconnects or the connection is closed.
+    ///
+    /// Requests that don't match the trace get an error response, and are
+    /// returned so that a test can check that the client behaved as recorded.
+    pub async fn run<R, W>(mut self, reader: R, writer: W) -> Result<Vec<Request>, FramingError>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut reader = FramedRead::new(reader, MessageCodec::new());
+        let mut writer = FramedWrite::new(writer, MessageCodec::new());
+        let mut next_seq = 1;
+        let mut unmatched = Vec::new();
+        let initial = std::mem::take(&mut self.initial);
+        write_all(&mut writer, &mut next_seq, initial).await?;
+        while let Some(message) = reader.next().await {
+            let request = match message {
+                Ok(Message::Request(request)) => request,
+                // Responses to reverse requests and unknown messages are ignored.
+                Ok(_) | Err(FramingError::Json(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            let disconnect = request.command == requests::Disconnect::COMMAND;
+            let messages = match self.respond(&request) {
+                Some(messages) => messages,
+                None => {
+                    let message = format!("no recorded response for `{}`", request.command);
+                    let response = Response::error(0, request.seq, &request.command, message, None);
+                    unmatched.push(request);
+                    vec![Message::Response(response)]
+                }
+            };
+            write_all(&mut writer, &mut next_seq, messages).await?;
+            if disconnect {
+                break;
+            }
+        }
+        Ok(unmatched)
+    }
+}
+
+/// Missing, `null` and empty arguments are all the same to an adapter.
+fn non_empty(arguments: &Option<Value>) -> Option<&Value> {
+    arguments
+        .as_ref()
+        .filter(|a| !a.is_null() && a.as_object().is_none_or(|a| !a.is_empty()))
+}
+
+async fn write_all<W: AsyncWrite + Unpin>(
+    writer: &mut FramedWrite<W, MessageCodec>,
+    next_seq: &mut u64,
+    messages: Vec<Message>,
+) -> Result<(), FramingError> {
+    for mut message in messages {
+        match &mut message {
+            Message::Request(request) => request.seq = *next_seq,
+            Message::Response(response) => response.seq = *next_seq,
+            Message::Event(event) => event.seq = *next_seq,
+        }
+        *next_seq += 1;
+        writer.send(message).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events;
+    use crate::messages::{Event, Events};
+    use serde_json::json;
+
+    fn client(seq: u64, command: &str, arguments: Value) -> TraceEntry {
+        TraceEntry {
+            direction: Direction::ClientToAdapter,
+            timestamp: 0,
+            message: json!({ "type": "request", "seq": seq, "command": command, "arguments": arguments }),
+        }
+    }
+
+    fn adapter(message: Message) -> TraceEntry {
+        TraceEntry {
+            direction: Direction::AdapterToClient,
+            timestamp: 0,
+            message: serde_json::to_value(message).unwrap(),
+        }
+    }
+
+    fn response(seq: u64, request_seq: u64, command: &str, body: Value) -> TraceEntry {
+        adapter(Message::Response(Response {
+            seq,
+            request_seq,
+            success: true,
+            command: command.to_owned(),
+            body: Some(body),
+            message: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        }))
+    }
+
+    fn trace() -> Vec<TraceEntry> {
+        let stopped = json!({ "reason": "step", "threadId": 1 });
+        vec![
+            adapter(Message::Event(Box::new(Event::new(
+                1,
+                Events::Initialized(None),
+            )))),
+            // Not a valid message, so it is not replayed.
+            TraceEntry {
+                direction: Direction::AdapterToClient,
+                timestamp: 0,
+                message: json!({ "type": "event", "seq": 2 }),
+            },
+            client(10, "next", json!({ "threadId": 1 })),
+            client(11, "threads", json!({})),
+            response(20, 11, "threads", json!({ "threads": [] })),
+            response(21, 10, "next", json!({})),
+            adapter(Message::Event(Box::new(Event::new(
+                22,
+                Events::new::<events::Stopped>(serde_json::from_value(stopped).unwrap()),
+            )))),
+            client(12, "threads", json!({})),
+            response(
+                22,
+                12,
+                "threads",
+                json!({ "threads": [{ "id": 1, "name": "main" }] }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn matches_command_and_arguments() {
+        let mut replay = Replay::new(trace());
+        let request = |seq, command: &str, arguments| Request {
+            seq,
+            command: command.to_owned(),
+            arguments: Some(arguments),
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        };
+
+        assert_eq!(
+            replay.respond(&request(1, "next", json!({ "threadId": 2 }))),
+            None
+        );
+        let next = replay
+            .respond(&request(1, "next", json!({ "threadId": 1 })))
+            .unwrap();
+        assert!(matches!(
+            next.as_slice(),
+            [Message::Response(r), Message::Event(e)]
+                if r.request_seq == 1 && r.command == "next" && matches!(e.event, Events::Stopped(_))
+        ));
+
+        let threads = |messages: Vec<Message>| match messages.as_slice() {
+            [Message::Response(r)] => {
+                assert_eq!(r.request_seq, 2);
+                r.body.as_ref().unwrap()["threads"]
+                    .as_array()
+                    .unwrap()
+                    .len()
+            }
+            other => panic!("unexpected messages {other:?}"),
+        };
+        let respond = |replay: &mut Replay| replay.respond(&request(2, "threads", json!({})));
+        assert_eq!(threads(respond(&mut replay).unwrap()), 0);
+        assert_eq!(threads(respond(&mut replay).unwrap()), 1);
+        assert_eq!(threads(respond(&mut replay).unwrap()), 1);
+    }
+
+    #[tokio::test]
+    async fn answers_client() {
+        let (client, adapter) = tokio::io::duplex(64);
+        let (reader, writer) = tokio::io::split(adapter);
+        let running = tokio::spawn(Replay::new(trace()).run(reader, writer));
+
+        let (reader, writer) = tokio::io::split(client);
+        let mut reader = FramedRead::new(reader, MessageCodec::new());
+        let mut writer = FramedWrite::new(writer, MessageCodec::new());
+        let mut next = async || reader.next().await.unwrap().unwrap();
+        assert!(matches!(
+            next().await,
+            Message::Event(e) if e.event == Events::Initialized(None) && e.seq == 1
+        ));
+
+        let request = |seq, command: &str| {
+            Message::Request(Request {
+                seq,
+                command: command.to_owned(),
+                arguments: Some(json!({})),
+                #[cfg(feature = "extra-fields")]
+                extra: Default::default(),
+            })
+        };
+        writer.send(request(1, "threads")).await.unwrap();
+        assert!(matches!(
+            next().await,
+            Message::Response(r) if (r.seq, r.request_seq, r.success) == (2, 1, true)
+        ));
+        writer.send(request(2, "stepBack")).await.unwrap();
+        assert!(matches!(
+            next().await,
+            Message::Response(r) if r.request_seq == 2 && !r.success
+        ));
+        writer.send(request(3, "disconnect")).await.unwrap();
+        assert!(matches!(next().await, Message::Response(r) if !r.success));
+
+        let unmatched = running.await.unwrap().unwrap();
+        let commands = unmatched
+            .iter()
+            .map(|r| r.command.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(commands, ["stepBack", "disconnect"]);
+    }
+}
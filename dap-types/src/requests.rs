@@ -1,12 +1,19 @@
 // This file is autogenerated. Do not edit by hand.
 // To regenerate from schema, run `cargo run -p generator`.
 
-use serde::{de::DeserializeOwned, Serialize};
+#![allow(clippy::doc_lazy_continuation)]
+
+use serde::{de::DeserializeOwned, Serialize, Serializer};
+use serde_json::Value;
 use std::fmt::Debug;
 
+use crate::messages::{decode_optional, OtherRequest, RequestParts};
+
 /// Request is a request, with associated command, and argument and response types.
 pub trait Request {
     const COMMAND: &'static str;
+    /// Adapter capability that has to be true for clients to send this request.
+    const REQUIRED_CAPABILITY: Option<&'static str> = None;
     type Arguments: Debug + Clone + Serialize + DeserializeOwned + Send + Sync;
     type Response: Debug + Clone + Serialize + DeserializeOwned + Send + Sync;
 }
@@ -25,6 +32,7 @@ pub enum Cancel {}
 
 impl Request for Cancel {
     const COMMAND: &'static str = "cancel";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsCancelRequest");
     type Arguments = crate::CancelArguments;
     type Response = ();
 }
@@ -72,6 +80,7 @@ pub enum ConfigurationDone {}
 
 impl Request for ConfigurationDone {
     const COMMAND: &'static str = "configurationDone";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsConfigurationDoneRequest");
     type Arguments = crate::ConfigurationDoneArguments;
     type Response = ();
 }
@@ -102,6 +111,7 @@ pub enum Restart {}
 
 impl Request for Restart {
     const COMMAND: &'static str = "restart";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsRestartRequest");
     type Arguments = crate::RestartArguments;
     type Response = ();
 }
@@ -125,6 +135,7 @@ pub enum Terminate {}
 
 impl Request for Terminate {
     const COMMAND: &'static str = "terminate";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsTerminateRequest");
     type Arguments = crate::TerminateArguments;
     type Response = ();
 }
@@ -135,6 +146,7 @@ pub enum BreakpointLocations {}
 
 impl Request for BreakpointLocations {
     const COMMAND: &'static str = "breakpointLocations";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsBreakpointLocationsRequest");
     type Arguments = crate::BreakpointLocationsArguments;
     type Response = crate::BreakpointLocationsResponse;
 }
@@ -158,6 +170,7 @@ pub enum SetFunctionBreakpoints {}
 
 impl Request for SetFunctionBreakpoints {
     const COMMAND: &'static str = "setFunctionBreakpoints";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsFunctionBreakpoints");
     type Arguments = crate::SetFunctionBreakpointsArguments;
     type Response = crate::SetFunctionBreakpointsResponse;
 }
@@ -178,6 +191,7 @@ pub enum DataBreakpointInfo {}
 
 impl Request for DataBreakpointInfo {
     const COMMAND: &'static str = "dataBreakpointInfo";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsDataBreakpoints");
     type Arguments = crate::DataBreakpointInfoArguments;
     type Response = crate::DataBreakpointInfoResponse;
 }
@@ -190,6 +204,7 @@ pub enum SetDataBreakpoints {}
 
 impl Request for SetDataBreakpoints {
     const COMMAND: &'static str = "setDataBreakpoints";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsDataBreakpoints");
     type Arguments = crate::SetDataBreakpointsArguments;
     type Response = crate::SetDataBreakpointsResponse;
 }
@@ -202,6 +217,7 @@ pub enum SetInstructionBreakpoints {}
 
 impl Request for SetInstructionBreakpoints {
     const COMMAND: &'static str = "setInstructionBreakpoints";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsInstructionBreakpoints");
     type Arguments = crate::SetInstructionBreakpointsArguments;
     type Response = crate::SetInstructionBreakpointsResponse;
 }
@@ -260,6 +276,7 @@ pub enum StepBack {}
 
 impl Request for StepBack {
     const COMMAND: &'static str = "stepBack";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsStepBack");
     type Arguments = crate::StepBackArguments;
     type Response = ();
 }
@@ -270,6 +287,7 @@ pub enum ReverseContinue {}
 
 impl Request for ReverseContinue {
     const COMMAND: &'static str = "reverseContinue";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsStepBack");
     type Arguments = crate::ReverseContinueArguments;
     type Response = ();
 }
@@ -281,6 +299,7 @@ pub enum RestartFrame {}
 
 impl Request for RestartFrame {
     const COMMAND: &'static str = "restartFrame";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsRestartFrame");
     type Arguments = crate::RestartFrameArguments;
     type Response = ();
 }
@@ -294,6 +313,7 @@ pub enum Goto {}
 
 impl Request for Goto {
     const COMMAND: &'static str = "goto";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsGotoTargetsRequest");
     type Arguments = crate::GotoArguments;
     type Response = ();
 }
@@ -343,6 +363,7 @@ pub enum SetVariable {}
 
 impl Request for SetVariable {
     const COMMAND: &'static str = "setVariable";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsSetVariable");
     type Arguments = crate::SetVariableArguments;
     type Response = crate::SetVariableResponse;
 }
@@ -371,6 +392,7 @@ pub enum TerminateThreads {}
 
 impl Request for TerminateThreads {
     const COMMAND: &'static str = "terminateThreads";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsTerminateThreadsRequest");
     type Arguments = crate::TerminateThreadsArguments;
     type Response = ();
 }
@@ -381,6 +403,7 @@ pub enum Modules {}
 
 impl Request for Modules {
     const COMMAND: &'static str = "modules";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsModulesRequest");
     type Arguments = crate::ModulesArguments;
     type Response = crate::ModulesResponse;
 }
@@ -391,6 +414,7 @@ pub enum LoadedSources {}
 
 impl Request for LoadedSources {
     const COMMAND: &'static str = "loadedSources";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsLoadedSourcesRequest");
     type Arguments = crate::LoadedSourcesArguments;
     type Response = crate::LoadedSourcesResponse;
 }
@@ -413,6 +437,7 @@ pub enum SetExpression {}
 
 impl Request for SetExpression {
     const COMMAND: &'static str = "setExpression";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsSetExpression");
     type Arguments = crate::SetExpressionArguments;
     type Response = crate::SetExpressionResponse;
 }
@@ -424,6 +449,7 @@ pub enum StepInTargets {}
 
 impl Request for StepInTargets {
     const COMMAND: &'static str = "stepInTargets";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsStepInTargetsRequest");
     type Arguments = crate::StepInTargetsArguments;
     type Response = crate::StepInTargetsResponse;
 }
@@ -435,6 +461,7 @@ pub enum GotoTargets {}
 
 impl Request for GotoTargets {
     const COMMAND: &'static str = "gotoTargets";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsGotoTargetsRequest");
     type Arguments = crate::GotoTargetsArguments;
     type Response = crate::GotoTargetsResponse;
 }
@@ -445,6 +472,7 @@ pub enum Completions {}
 
 impl Request for Completions {
     const COMMAND: &'static str = "completions";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsCompletionsRequest");
     type Arguments = crate::CompletionsArguments;
     type Response = crate::CompletionsResponse;
 }
@@ -455,6 +483,7 @@ pub enum ExceptionInfo {}
 
 impl Request for ExceptionInfo {
     const COMMAND: &'static str = "exceptionInfo";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsExceptionInfoRequest");
     type Arguments = crate::ExceptionInfoArguments;
     type Response = crate::ExceptionInfoResponse;
 }
@@ -465,6 +494,7 @@ pub enum ReadMemory {}
 
 impl Request for ReadMemory {
     const COMMAND: &'static str = "readMemory";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsReadMemoryRequest");
     type Arguments = crate::ReadMemoryArguments;
     type Response = crate::ReadMemoryResponse;
 }
@@ -475,6 +505,7 @@ pub enum WriteMemory {}
 
 impl Request for WriteMemory {
     const COMMAND: &'static str = "writeMemory";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsWriteMemoryRequest");
     type Arguments = crate::WriteMemoryArguments;
     type Response = crate::WriteMemoryResponse;
 }
@@ -485,6 +516,7 @@ pub enum Disassemble {}
 
 impl Request for Disassemble {
     const COMMAND: &'static str = "disassemble";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsDisassembleRequest");
     type Arguments = crate::DisassembleArguments;
     type Response = crate::DisassembleResponse;
 }
@@ -497,3 +529,570 @@ impl Request for Locations {
     type Arguments = crate::LocationsArguments;
     type Response = crate::LocationsResponse;
 }
+
+/// Requests that clients should only send if the adapter advertises a capability, as `(command, capability)` pairs.
+pub const CAPABILITY_GATED_REQUESTS: &[(&str, &str)] = &[
+    ("cancel", "supportsCancelRequest"),
+    ("configurationDone", "supportsConfigurationDoneRequest"),
+    ("restart", "supportsRestartRequest"),
+    ("terminate", "supportsTerminateRequest"),
+    ("breakpointLocations", "supportsBreakpointLocationsRequest"),
+    ("setFunctionBreakpoints", "supportsFunctionBreakpoints"),
+    ("dataBreakpointInfo", "supportsDataBreakpoints"),
+    ("setDataBreakpoints", "supportsDataBreakpoints"),
+    (
+        "setInstructionBreakpoints",
+        "supportsInstructionBreakpoints",
+    ),
+    ("stepBack", "supportsStepBack"),
+    ("reverseContinue", "supportsStepBack"),
+    ("restartFrame", "supportsRestartFrame"),
+    ("goto", "supportsGotoTargetsRequest"),
+    ("setVariable", "supportsSetVariable"),
+    ("terminateThreads", "supportsTerminateThreadsRequest"),
+    ("modules", "supportsModulesRequest"),
+    ("loadedSources", "supportsLoadedSourcesRequest"),
+    ("setExpression", "supportsSetExpression"),
+    ("stepInTargets", "supportsStepInTargetsRequest"),
+    ("gotoTargets", "supportsGotoTargetsRequest"),
+    ("completions", "supportsCompletionsRequest"),
+    ("exceptionInfo", "supportsExceptionInfoRequest"),
+    ("readMemory", "supportsReadMemoryRequest"),
+    ("writeMemory", "supportsWriteMemoryRequest"),
+    ("disassemble", "supportsDisassembleRequest"),
+];
+
+/// Argument fields that adapters only honor if they advertise a capability, as `(command, field path, capability)` triples.
+///
+/// Field paths are field names joined by dots, which apply to every element of arrays along the way.
+pub const CAPABILITY_GATED_FIELDS: &[(&str, &str, &str)] = &[
+    (
+        "disconnect",
+        "terminateDebuggee",
+        "supportTerminateDebuggee",
+    ),
+    ("disconnect", "suspendDebuggee", "supportSuspendDebuggee"),
+    (
+        "setBreakpoints",
+        "breakpoints.condition",
+        "supportsConditionalBreakpoints",
+    ),
+    (
+        "setBreakpoints",
+        "breakpoints.hitCondition",
+        "supportsHitConditionalBreakpoints",
+    ),
+    (
+        "setBreakpoints",
+        "breakpoints.logMessage",
+        "supportsLogPoints",
+    ),
+    (
+        "setFunctionBreakpoints",
+        "breakpoints.condition",
+        "supportsConditionalBreakpoints",
+    ),
+    (
+        "setFunctionBreakpoints",
+        "breakpoints.hitCondition",
+        "supportsHitConditionalBreakpoints",
+    ),
+    (
+        "setExceptionBreakpoints",
+        "filterOptions",
+        "supportsExceptionFilterOptions",
+    ),
+    (
+        "setExceptionBreakpoints",
+        "exceptionOptions",
+        "supportsExceptionOptions",
+    ),
+    ("dataBreakpointInfo", "bytes", "supportsDataBreakpointBytes"),
+    (
+        "dataBreakpointInfo",
+        "asAddress",
+        "supportsDataBreakpointBytes",
+    ),
+    (
+        "setDataBreakpoints",
+        "breakpoints.condition",
+        "supportsConditionalBreakpoints",
+    ),
+    (
+        "setDataBreakpoints",
+        "breakpoints.hitCondition",
+        "supportsHitConditionalBreakpoints",
+    ),
+    (
+        "setInstructionBreakpoints",
+        "breakpoints.condition",
+        "supportsConditionalBreakpoints",
+    ),
+    (
+        "setInstructionBreakpoints",
+        "breakpoints.hitCondition",
+        "supportsHitConditionalBreakpoints",
+    ),
+    (
+        "continue",
+        "singleThread",
+        "supportsSingleThreadExecutionRequests",
+    ),
+    (
+        "next",
+        "singleThread",
+        "supportsSingleThreadExecutionRequests",
+    ),
+    ("next", "granularity", "supportsSteppingGranularity"),
+    (
+        "stepIn",
+        "singleThread",
+        "supportsSingleThreadExecutionRequests",
+    ),
+    ("stepIn", "granularity", "supportsSteppingGranularity"),
+    (
+        "stepOut",
+        "singleThread",
+        "supportsSingleThreadExecutionRequests",
+    ),
+    ("stepOut", "granularity", "supportsSteppingGranularity"),
+    (
+        "stepBack",
+        "singleThread",
+        "supportsSingleThreadExecutionRequests",
+    ),
+    ("stepBack", "granularity", "supportsSteppingGranularity"),
+    (
+        "reverseContinue",
+        "singleThread",
+        "supportsSingleThreadExecutionRequests",
+    ),
+    ("stackTrace", "format", "supportsValueFormattingOptions"),
+    (
+        "stackTrace",
+        "startFrame",
+        "supportsDelayedStackTraceLoading",
+    ),
+    ("stackTrace", "levels", "supportsDelayedStackTraceLoading"),
+    ("variables", "format", "supportsValueFormattingOptions"),
+    ("setVariable", "format", "supportsValueFormattingOptions"),
+    ("evaluate", "format", "supportsValueFormattingOptions"),
+    ("setExpression", "format", "supportsValueFormattingOptions"),
+];
+
+/// A request with typed arguments.
+/// Requests with commands not defined by the protocol are decoded as `Other`.
+#[derive(Debug, Clone)]
+pub enum Requests {
+    Cancel(crate::CancelArguments),
+    RunInTerminal(crate::RunInTerminalRequestArguments),
+    StartDebugging(crate::StartDebuggingRequestArguments),
+    Initialize(crate::InitializeRequestArguments),
+    ConfigurationDone(crate::ConfigurationDoneArguments),
+    Launch(crate::LaunchRequestArguments),
+    Attach(crate::AttachRequestArguments),
+    Restart(crate::RestartArguments),
+    Disconnect(crate::DisconnectArguments),
+    Terminate(crate::TerminateArguments),
+    BreakpointLocations(crate::BreakpointLocationsArguments),
+    SetBreakpoints(crate::SetBreakpointsArguments),
+    SetFunctionBreakpoints(crate::SetFunctionBreakpointsArguments),
+    SetExceptionBreakpoints(crate::SetExceptionBreakpointsArguments),
+    DataBreakpointInfo(crate::DataBreakpointInfoArguments),
+    SetDataBreakpoints(crate::SetDataBreakpointsArguments),
+    SetInstructionBreakpoints(crate::SetInstructionBreakpointsArguments),
+    Continue(crate::ContinueArguments),
+    Next(crate::NextArguments),
+    StepIn(crate::StepInArguments),
+    StepOut(crate::StepOutArguments),
+    StepBack(crate::StepBackArguments),
+    ReverseContinue(crate::ReverseContinueArguments),
+    RestartFrame(crate::RestartFrameArguments),
+    Goto(crate::GotoArguments),
+    Pause(crate::PauseArguments),
+    StackTrace(crate::StackTraceArguments),
+    Scopes(crate::ScopesArguments),
+    Variables(crate::VariablesArguments),
+    SetVariable(crate::SetVariableArguments),
+    Source(crate::SourceArguments),
+    Threads(crate::ThreadsArgument),
+    TerminateThreads(crate::TerminateThreadsArguments),
+    Modules(crate::ModulesArguments),
+    LoadedSources(crate::LoadedSourcesArguments),
+    Evaluate(crate::EvaluateArguments),
+    SetExpression(crate::SetExpressionArguments),
+    StepInTargets(crate::StepInTargetsArguments),
+    GotoTargets(crate::GotoTargetsArguments),
+    Completions(crate::CompletionsArguments),
+    ExceptionInfo(crate::ExceptionInfoArguments),
+    ReadMemory(crate::ReadMemoryArguments),
+    WriteMemory(crate::WriteMemoryArguments),
+    Disassemble(crate::DisassembleArguments),
+    Locations(crate::LocationsArguments),
+    Other(OtherRequest),
+}
+
+impl Requests {
+    /// Decodes request arguments according to the command.
+    pub fn from_parts(
+        command: String,
+        arguments: Option<Value>,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(match command.as_str() {
+            "cancel" => Requests::Cancel(decode_optional(arguments)?),
+            "runInTerminal" => Requests::RunInTerminal(decode_optional(arguments)?),
+            "startDebugging" => Requests::StartDebugging(decode_optional(arguments)?),
+            "initialize" => Requests::Initialize(decode_optional(arguments)?),
+            "configurationDone" => Requests::ConfigurationDone(decode_optional(arguments)?),
+            "launch" => Requests::Launch(decode_optional(arguments)?),
+            "attach" => Requests::Attach(decode_optional(arguments)?),
+            "restart" => Requests::Restart(decode_optional(arguments)?),
+            "disconnect" => Requests::Disconnect(decode_optional(arguments)?),
+            "terminate" => Requests::Terminate(decode_optional(arguments)?),
+            "breakpointLocations" => Requests::BreakpointLocations(decode_optional(arguments)?),
+            "setBreakpoints" => Requests::SetBreakpoints(decode_optional(arguments)?),
+            "setFunctionBreakpoints" => {
+                Requests::SetFunctionBreakpoints(decode_optional(arguments)?)
+            }
+            "setExceptionBreakpoints" => {
+                Requests::SetExceptionBreakpoints(decode_optional(arguments)?)
+            }
+            "dataBreakpointInfo" => Requests::DataBreakpointInfo(decode_optional(arguments)?),
+            "setDataBreakpoints" => Requests::SetDataBreakpoints(decode_optional(arguments)?),
+            "setInstructionBreakpoints" => {
+                Requests::SetInstructionBreakpoints(decode_optional(arguments)?)
+            }
+            "continue" => Requests::Continue(decode_optional(arguments)?),
+            "next" => Requests::Next(decode_optional(arguments)?),
+            "stepIn" => Requests::StepIn(decode_optional(arguments)?),
+            "stepOut" => Requests::StepOut(decode_optional(arguments)?),
+            "stepBack" => Requests::StepBack(decode_optional(arguments)?),
+            "reverseContinue" => Requests::ReverseContinue(decode_optional(arguments)?),
+            "restartFrame" => Requests::RestartFrame(decode_optional(arguments)?),
+            "goto" => Requests::Goto(decode_optional(arguments)?),
+            "pause" => Requests::Pause(decode_optional(arguments)?),
+            "stackTrace" => Requests::StackTrace(decode_optional(arguments)?),
+            "scopes" => Requests::Scopes(decode_optional(arguments)?),
+            "variables" => Requests::Variables(decode_optional(arguments)?),
+            "setVariable" => Requests::SetVariable(decode_optional(arguments)?),
+            "source" => Requests::Source(decode_optional(arguments)?),
+            "threads" => Requests::Threads(decode_optional(arguments)?),
+            "terminateThreads" => Requests::TerminateThreads(decode_optional(arguments)?),
+            "modules" => Requests::Modules(decode_optional(arguments)?),
+            "loadedSources" => Requests::LoadedSources(decode_optional(arguments)?),
+            "evaluate" => Requests::Evaluate(decode_optional(arguments)?),
+            "setExpression" => Requests::SetExpression(decode_optional(arguments)?),
+            "stepInTargets" => Requests::StepInTargets(decode_optional(arguments)?),
+            "gotoTargets" => Requests::GotoTargets(decode_optional(arguments)?),
+            "completions" => Requests::Completions(decode_optional(arguments)?),
+            "exceptionInfo" => Requests::ExceptionInfo(decode_optional(arguments)?),
+            "readMemory" => Requests::ReadMemory(decode_optional(arguments)?),
+            "writeMemory" => Requests::WriteMemory(decode_optional(arguments)?),
+            "disassemble" => Requests::Disassemble(decode_optional(arguments)?),
+            "locations" => Requests::Locations(decode_optional(arguments)?),
+            _ => Requests::Other(OtherRequest { command, arguments }),
+        })
+    }
+
+    /// Encodes request arguments, returning the command and the arguments.
+    pub fn into_parts(self) -> Result<(String, Option<Value>), serde_json::Error> {
+        let command = self.command().to_owned();
+        let arguments = match self {
+            Requests::Cancel(arguments) => serde_json::to_value(arguments)?,
+            Requests::RunInTerminal(arguments) => serde_json::to_value(arguments)?,
+            Requests::StartDebugging(arguments) => serde_json::to_value(arguments)?,
+            Requests::Initialize(arguments) => serde_json::to_value(arguments)?,
+            Requests::ConfigurationDone(arguments) => serde_json::to_value(arguments)?,
+            Requests::Launch(arguments) => serde_json::to_value(arguments)?,
+            Requests::Attach(arguments) => serde_json::to_value(arguments)?,
+            Requests::Restart(arguments) => serde_json::to_value(arguments)?,
+            Requests::Disconnect(arguments) => serde_json::to_value(arguments)?,
+            Requests::Terminate(arguments) => serde_json::to_value(arguments)?,
+            Requests::BreakpointLocations(arguments) => serde_json::to_value(arguments)?,
+            Requests::SetBreakpoints(arguments) => serde_json::to_value(arguments)?,
+            Requests::SetFunctionBreakpoints(arguments) => serde_json::to_value(arguments)?,
+            Requests::SetExceptionBreakpoints(arguments) => serde_json::to_value(arguments)?,
+            Requests::DataBreakpointInfo(arguments) => serde_json::to_value(arguments)?,
+            Requests::SetDataBreakpoints(arguments) => serde_json::to_value(arguments)?,
+            Requests::SetInstructionBreakpoints(arguments) => serde_json::to_value(arguments)?,
+            Requests::Continue(arguments) => serde_json::to_value(arguments)?,
+            Requests::Next(arguments) => serde_json::to_value(arguments)?,
+            Requests::StepIn(arguments) => serde_json::to_value(arguments)?,
+            Requests::StepOut(arguments) => serde_json::to_value(arguments)?,
+            Requests::StepBack(arguments) => serde_json::to_value(arguments)?,
+            Requests::ReverseContinue(arguments) => serde_json::to_value(arguments)?,
+            Requests::RestartFrame(arguments) => serde_json::to_value(arguments)?,
+            Requests::Goto(arguments) => serde_json::to_value(arguments)?,
+            Requests::Pause(arguments) => serde_json::to_value(arguments)?,
+            Requests::StackTrace(arguments) => serde_json::to_value(arguments)?,
+            Requests::Scopes(arguments) => serde_json::to_value(arguments)?,
+            Requests::Variables(arguments) => serde_json::to_value(arguments)?,
+            Requests::SetVariable(arguments) => serde_json::to_value(arguments)?,
+            Requests::Source(arguments) => serde_json::to_value(arguments)?,
+            Requests::Threads(arguments) => serde_json::to_value(arguments)?,
+            Requests::TerminateThreads(arguments) => serde_json::to_value(arguments)?,
+            Requests::Modules(arguments) => serde_json::to_value(arguments)?,
+            Requests::LoadedSources(arguments) => serde_json::to_value(arguments)?,
+            Requests::Evaluate(arguments) => serde_json::to_value(arguments)?,
+            Requests::SetExpression(arguments) => serde_json::to_value(arguments)?,
+            Requests::StepInTargets(arguments) => serde_json::to_value(arguments)?,
+            Requests::GotoTargets(arguments) => serde_json::to_value(arguments)?,
+            Requests::Completions(arguments) => serde_json::to_value(arguments)?,
+            Requests::ExceptionInfo(arguments) => serde_json::to_value(arguments)?,
+            Requests::ReadMemory(arguments) => serde_json::to_value(arguments)?,
+            Requests::WriteMemory(arguments) => serde_json::to_value(arguments)?,
+            Requests::Disassemble(arguments) => serde_json::to_value(arguments)?,
+            Requests::Locations(arguments) => serde_json::to_value(arguments)?,
+            Requests::Other(other) => return Ok((command, other.arguments)),
+        };
+        Ok((command, Some(arguments).filter(|a| !a.is_null())))
+    }
+
+    pub fn command(&self) -> &str {
+        match self {
+            Requests::Cancel(_) => Cancel::COMMAND,
+            Requests::RunInTerminal(_) => RunInTerminal::COMMAND,
+            Requests::StartDebugging(_) => StartDebugging::COMMAND,
+            Requests::Initialize(_) => Initialize::COMMAND,
+            Requests::ConfigurationDone(_) => ConfigurationDone::COMMAND,
+            Requests::Launch(_) => Launch::COMMAND,
+            Requests::Attach(_) => Attach::COMMAND,
+            Requests::Restart(_) => Restart::COMMAND,
+            Requests::Disconnect(_) => Disconnect::COMMAND,
+            Requests::Terminate(_) => Terminate::COMMAND,
+            Requests::BreakpointLocations(_) => BreakpointLocations::COMMAND,
+            Requests::SetBreakpoints(_) => SetBreakpoints::COMMAND,
+            Requests::SetFunctionBreakpoints(_) => SetFunctionBreakpoints::COMMAND,
+            Requests::SetExceptionBreakpoints(_) => SetExceptionBreakpoints::COMMAND,
+            Requests::DataBreakpointInfo(_) => DataBreakpointInfo::COMMAND,
+            Requests::SetDataBreakpoints(_) => SetDataBreakpoints::COMMAND,
+            Requests::SetInstructionBreakpoints(_) => SetInstructionBreakpoints::COMMAND,
+            Requests::Continue(_) => Continue::COMMAND,
+            Requests::Next(_) => Next::COMMAND,
+            Requests::StepIn(_) => StepIn::COMMAND,
+            Requests::StepOut(_) => StepOut::COMMAND,
+            Requests::StepBack(_) => StepBack::COMMAND,
+            Requests::ReverseContinue(_) => ReverseContinue::COMMAND,
+            Requests::RestartFrame(_) => RestartFrame::COMMAND,
+            Requests::Goto(_) => Goto::COMMAND,
+            Requests::Pause(_) => Pause::COMMAND,
+            Requests::StackTrace(_) => StackTrace::COMMAND,
+            Requests::Scopes(_) => Scopes::COMMAND,
+            Requests::Variables(_) => Variables::COMMAND,
+            Requests::SetVariable(_) => SetVariable::COMMAND,
+            Requests::Source(_) => Source::COMMAND,
+            Requests::Threads(_) => Threads::COMMAND,
+            Requests::TerminateThreads(_) => TerminateThreads::COMMAND,
+            Requests::Modules(_) => Modules::COMMAND,
+            Requests::LoadedSources(_) => LoadedSources::COMMAND,
+            Requests::Evaluate(_) => Evaluate::COMMAND,
+            Requests::SetExpression(_) => SetExpression::COMMAND,
+            Requests::StepInTargets(_) => StepInTargets::COMMAND,
+            Requests::GotoTargets(_) => GotoTargets::COMMAND,
+            Requests::Completions(_) => Completions::COMMAND,
+            Requests::ExceptionInfo(_) => ExceptionInfo::COMMAND,
+            Requests::ReadMemory(_) => ReadMemory::COMMAND,
+            Requests::WriteMemory(_) => WriteMemory::COMMAND,
+            Requests::Disassemble(_) => Disassemble::COMMAND,
+            Requests::Locations(_) => Locations::COMMAND,
+            Requests::Other(other) => &other.command,
+        }
+    }
+}
+
+impl Serialize for Requests {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let command = self.command();
+        match self {
+            Requests::Cancel(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::RunInTerminal(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::StartDebugging(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Initialize(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::ConfigurationDone(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Launch(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Attach(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Restart(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Disconnect(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Terminate(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::BreakpointLocations(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::SetBreakpoints(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::SetFunctionBreakpoints(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::SetExceptionBreakpoints(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::DataBreakpointInfo(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::SetDataBreakpoints(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::SetInstructionBreakpoints(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Continue(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Next(arguments) => RequestParts { command, arguments }.serialize(serializer),
+            Requests::StepIn(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::StepOut(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::StepBack(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::ReverseContinue(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::RestartFrame(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Goto(arguments) => RequestParts { command, arguments }.serialize(serializer),
+            Requests::Pause(arguments) => RequestParts { command, arguments }.serialize(serializer),
+            Requests::StackTrace(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Scopes(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Variables(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::SetVariable(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Source(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Threads(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::TerminateThreads(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Modules(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::LoadedSources(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Evaluate(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::SetExpression(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::StepInTargets(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::GotoTargets(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Completions(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::ExceptionInfo(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::ReadMemory(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::WriteMemory(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Disassemble(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Locations(arguments) => {
+                RequestParts { command, arguments }.serialize(serializer)
+            }
+            Requests::Other(other) => other.serialize(serializer),
+        }
+    }
+}
+
+/// Checks that the body of a successful response fits the response type of the command.
+/// Returns `None` for commands not defined by the protocol.
+pub(crate) fn check_response_body(
+    command: &str,
+    body: Option<Value>,
+) -> Option<Result<(), serde_json::Error>> {
+    fn check<R: Request>(body: Option<Value>) -> Result<(), serde_json::Error> {
+        decode_optional::<R::Response>(body).map(drop)
+    }
+    Some(match command {
+        "cancel" => check::<Cancel>(body),
+        "runInTerminal" => check::<RunInTerminal>(body),
+        "startDebugging" => check::<StartDebugging>(body),
+        "initialize" => check::<Initialize>(body),
+        "configurationDone" => check::<ConfigurationDone>(body),
+        "launch" => check::<Launch>(body),
+        "attach" => check::<Attach>(body),
+        "restart" => check::<Restart>(body),
+        "disconnect" => check::<Disconnect>(body),
+        "terminate" => check::<Terminate>(body),
+        "breakpointLocations" => check::<BreakpointLocations>(body),
+        "setBreakpoints" => check::<SetBreakpoints>(body),
+        "setFunctionBreakpoints" => check::<SetFunctionBreakpoints>(body),
+        "setExceptionBreakpoints" => check::<SetExceptionBreakpoints>(body),
+        "dataBreakpointInfo" => check::<DataBreakpointInfo>(body),
+        "setDataBreakpoints" => check::<SetDataBreakpoints>(body),
+        "setInstructionBreakpoints" => check::<SetInstructionBreakpoints>(body),
+        "continue" => check::<Continue>(body),
+        "next" => check::<Next>(body),
+        "stepIn" => check::<StepIn>(body),
+        "stepOut" => check::<StepOut>(body),
+        "stepBack" => check::<StepBack>(body),
+        "reverseContinue" => check::<ReverseContinue>(body),
+        "restartFrame" => check::<RestartFrame>(body),
+        "goto" => check::<Goto>(body),
+        "pause" => check::<Pause>(body),
+        "stackTrace" => check::<StackTrace>(body),
+        "scopes" => check::<Scopes>(body),
+        "variables" => check::<Variables>(body),
+        "setVariable" => check::<SetVariable>(body),
+        "source" => check::<Source>(body),
+        "threads" => check::<Threads>(body),
+        "terminateThreads" => check::<TerminateThreads>(body),
+        "modules" => check::<Modules>(body),
+        "loadedSources" => check::<LoadedSources>(body),
+        "evaluate" => check::<Evaluate>(body),
+        "setExpression" => check::<SetExpression>(body),
+        "stepInTargets" => check::<StepInTargets>(body),
+        "gotoTargets" => check::<GotoTargets>(body),
+        "completions" => check::<Completions>(body),
+        "exceptionInfo" => check::<ExceptionInfo>(body),
+        "readMemory" => check::<ReadMemory>(body),
+        "writeMemory" => check::<WriteMemory>(body),
+        "disassemble" => check::<Disassemble>(body),
+        "locations" => check::<Locations>(body),
+        _ => return None,
+    })
+}
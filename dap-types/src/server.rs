@@ -0,0 +1,235 @@
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+use crate::adapter::{dispatch, DebugAdapter};
+use crate::codec::MessageCodec;
+use crate::events;
+use crate::framing::FramingError;
+use crate::messages::{Event, Events, Message};
+use crate::requests::{self, Request as _};
+
+/// Runs a [`DebugAdapter`] over a transport.
+///
+/// Requests are handled one at a time, in the order they arrive. Messages sent
+/// through a [`Sender`] while a request is being handled are written before the
+/// response to that request.
+pub struct Server<R, W> {
+    reader: FramedRead<R, MessageCodec>,
+    writer: FramedWrite<W, MessageCodec>,
+    outgoing: mpsc::UnboundedReceiver<Message>,
+    sender: Sender,
+    next_seq: u64,
+}
+
+/// Handle for sending events to the client, which can be kept by the adapter.
+#[derive(Debug, Clone)]
+pub struct Sender {
+    outgoing: mpsc::UnboundedSender<Message>,
+}
+
+impl<R, W> Server<R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    pub fn new(reader: R, writer: W) -> (Self, Sender) {
+        let (outgoing, outgoing_rx) = mpsc::unbounded_channel();
+        let sender = Sender { outgoing };
+        let server = Server {
+            reader: FramedRead::new(reader, MessageCodec::new()),
+            writer: FramedWrite::new(writer, MessageCodec::new()),
+            outgoing: outgoing_rx,
+            sender: sender.clone(),
+            next_seq: 1,
+        };
+        (server, sender)
+    }
+
+    /// Handles requests until the client disconnects or the connection is closed.
+    ///
+    /// Returns after the response to a `disconnect` request has been written.
+    pub async fn run<A: DebugAdapter>(mut self, adapter: &mut A) -> Result<(), FramingError> {
+        loop {
+            let next = tokio::select! {
+                message = self.reader.next() => Next::Incoming(message),
+                Some(message) = self.outgoing.recv() => Next::Outgoing(message),
+            };
+            let message = match next {
+                Next::Incoming(message) => message,
+                Next::Outgoing(message) => {
+                    self.write(message).await?;
+                    continue;
+                }
+            };
+            let request = match message {
+                Some(Ok(Message::Request(request))) => request,
+                // Responses to reverse requests and unknown messages are ignored.
+                Some(Ok(_)) | Some(Err(FramingError::Json(_))) => continue,
+                Some(Err(e)) => return Err(e),
+                None => break,
+            };
+            let response = dispatch(adapter, &request).await;
+            self.sender.send(Message::Response(response));
+            if request.command == requests::Disconnect::COMMAND {
+                break;
+            }
+        }
+        while let Ok(message) = self.outgoing.try_recv() {
+            self.write(message).await?;
+        }
+        Ok(())
+    }
+
+    async fn write(&mut self, mut message: Message) -> Result<(), FramingError> {
+        match &mut message {
+            Message::Request(request) => request.seq = self.next_seq,
+            Message::Response(response) => response.seq = self.next_seq,
+            Message::Event(event) => event.seq = self.next_seq,
+        }
+        self.next_seq += 1;
+        self.writer.send(message).await
+    }
+}
+
+enum Next {
+    Incoming(Option<Result<Message, FramingError>>),
+    Outgoing(Message),
+}
+
+impl Sender {
+    /// Queues an event to be sent to the client.
+    ///
+    /// Returns false if the server is no longer running.
+    pub fn send_event(&self, event: Events) -> bool {
+        self.send(Message::Event(Box::new(Event::new(0, event))))
+    }
+
+    /// Queues event `E` to be sent to the client.
+    ///
+    /// Returns false if the server is no longer running.
+    pub fn event<E: events::Event>(&self, body: E::Body) -> bool {
+        self.send_event(Events::new::<E>(body))
+    }
+
+    fn send(&self, message: Message) -> bool {
+        self.outgoing.send(message).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::HandlerResult;
+    use crate::messages::{Request, Response};
+
+    struct TestAdapter {
+        sender: Sender,
+    }
+
+    impl DebugAdapter for TestAdapter {
+        async fn initialize(
+            &mut self,
+            _: crate::InitializeRequestArguments,
+        ) -> HandlerResult<crate::Capabilities> {
+            Ok(crate::Capabilities {
+                supports_configuration_done_request: Some(true),
+                ..Default::default()
+            })
+        }
+
+        async fn configuration_done(
+            &mut self,
+            _: crate::ConfigurationDoneArguments,
+        ) -> HandlerResult<()> {
+            self.sender.event::<events::Initialized>(None);
+            Ok(())
+        }
+    }
+
+    fn request(seq: u64, command: &str, arguments: serde_json::Value) -> Message {
+        Message::Request(Request {
+            seq,
+            command: command.to_owned(),
+            arguments: Some(arguments),
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn dispatches_requests() {
+        let (client, adapter) = tokio::io::duplex(64);
+        let (reader, writer) = tokio::io::split(adapter);
+        let (server, sender) = Server::new(reader, writer);
+        let running = tokio::spawn(async move { server.run(&mut TestAdapter { sender }).await });
+
+        let (reader, writer) = tokio::io::split(client);
+        let mut reader = FramedRead::new(reader, MessageCodec::new());
+        let mut writer = FramedWrite::new(writer, MessageCodec::new());
+        let mut response = async || match reader.next().await {
+            Some(Ok(Message::Response(response))) => response,
+            Some(Ok(Message::Event(event))) => panic!("unexpected event {}", event.event),
+            other => panic!("unexpected message {other:?}"),
+        };
+
+        let initialize = serde_json::json!({ "adapterID": "test" });
+        writer
+            .send(request(1, "initialize", initialize))
+            .await
+            .unwrap();
+        let capabilities = response().await.into_typed::<requests::Initialize>();
+        assert_eq!(
+            capabilities.unwrap().supports_configuration_done_request,
+            Some(true)
+        );
+
+        writer
+            .send(request(2, "stepBack", serde_json::json!({ "threadId": 1 })))
+            .await
+            .unwrap();
+        let Response {
+            seq,
+            request_seq,
+            success,
+            message,
+            ..
+        } = response().await;
+        assert_eq!((seq, request_seq, success), (2, 2, false));
+        assert_eq!(message.as_deref(), Some("unsupported request: stepBack"));
+
+        writer
+            .send(request(3, "disconnect", serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(response().await.request_seq, 3);
+        running.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn events_are_written_before_response() {
+        let (client, adapter) = tokio::io::duplex(64);
+        let (reader, writer) = tokio::io::split(adapter);
+        let (server, sender) = Server::new(reader, writer);
+        tokio::spawn(async move { server.run(&mut TestAdapter { sender }).await });
+
+        let (reader, writer) = tokio::io::split(client);
+        let mut reader = FramedRead::new(reader, MessageCodec::new());
+        let mut writer = FramedWrite::new(writer, MessageCodec::new());
+        writer
+            .send(request(1, "configurationDone", serde_json::json!({})))
+            .await
+            .unwrap();
+        assert!(matches!(
+            reader.next().await,
+            Some(Ok(Message::Event(event)))
+                if event.event == Events::Initialized(None) && event.seq == 1
+        ));
+        assert!(matches!(
+            reader.next().await,
+            Some(Ok(Message::Response(response)))
+                if response.request_seq == 1 && response.seq == 2
+        ));
+    }
+}
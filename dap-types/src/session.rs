@@ -0,0 +1,337 @@
+use crate::messages::{Events, Message, Request, Response};
+use crate::requests::{self, Request as _};
+
+/// Requests that configure the adapter, which can only be sent after the
+/// `initialized` event.
+const CONFIGURATION_REQUESTS: &[&str] = &[
+    requests::SetBreakpoints::COMMAND,
+    requests::SetFunctionBreakpoints::COMMAND,
+    requests::SetExceptionBreakpoints::COMMAND,
+    requests::SetDataBreakpoints::COMMAND,
+    requests::SetInstructionBreakpoints::COMMAND,
+    requests::ConfigurationDone::COMMAND,
+];
+
+/// Requests that are sent by the adapter, which don't take part in the handshake.
+const REVERSE_REQUESTS: &[&str] = &[
+    requests::RunInTerminal::COMMAND,
+    requests::StartDebugging::COMMAND,
+];
+
+/// Phase of a debug session, from the client's first request until it disconnects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// `initialize` has not been sent yet, it is the only legal request.
+    Uninitialized,
+    /// `initialize` was sent, and no request is legal until it is answered.
+    Initializing,
+    /// The adapter responded to `initialize`, but has not sent the `initialized`
+    /// event yet. `launch` or `attach` can be sent, but not configuration requests.
+    WaitingForConfiguration,
+    /// The adapter sent the `initialized` event and accepts configuration
+    /// requests until `configurationDone`.
+    Configuring,
+    /// `configurationDone` was sent.
+    Running,
+    /// `disconnect` was sent, no more requests are legal.
+    Disconnected,
+}
+
+/// Tracks the initialize and configuration handshake of a session.
+///
+/// Every message exchanged between the client and the adapter should be passed
+/// to [`SessionState::observe`] in the order they were sent, which reports
+/// messages that break the order described by the protocol:
+///
+/// 1. `initialize` request and its response,
+/// 2. `initialized` event,
+/// 3. `setBreakpoints`, `setFunctionBreakpoints`, `setExceptionBreakpoints` and
+///    other configuration requests,
+/// 4. `configurationDone` request.
+///
+/// `launch` or `attach` can be sent at any point after the `initialize` response.
+#[derive(Debug, Clone)]
+pub struct SessionState {
+    phase: Phase,
+    initialize_seq: Option<u64>,
+    launched: bool,
+}
+
+impl SessionState {
+    pub fn new() -> Self {
+        SessionState {
+            phase: Phase::Uninitialized,
+            initialize_seq: None,
+            launched: false,
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Returns whether the client can send a request with the given command now.
+    pub fn allows(&self, command: &str) -> bool {
+        self.check_request(command).is_ok()
+    }
+
+    /// Checks whether the client can send a request with the given command now.
+    pub fn check_request(&self, command: &str) -> Result<(), SessionError> {
+        let violation = |kind| {
+            Err(SessionError {
+                phase: self.phase,
+                command: Some(command.to_owned()),
+                kind,
+            })
+        };
+        let is_initialize = command == requests::Initialize::COMMAND;
+        let is_launch =
+            command == requests::Launch::COMMAND || command == requests::Attach::COMMAND;
+        match self.phase {
+            Phase::Uninitialized if !is_initialize => violation(ViolationKind::NotInitialized),
+            Phase::Initializing if is_initialize => violation(ViolationKind::AlreadyInitialized),
+            Phase::Initializing => violation(ViolationKind::NotInitialized),
+            Phase::Disconnected => violation(ViolationKind::Disconnected),
+            Phase::WaitingForConfiguration | Phase::Configuring | Phase::Running
+                if is_initialize =>
+            {
+                violation(ViolationKind::AlreadyInitialized)
+            }
+            Phase::WaitingForConfiguration if CONFIGURATION_REQUESTS.contains(&command) => {
+                violation(ViolationKind::NotConfigurable)
+            }
+            Phase::Running if command == requests::ConfigurationDone::COMMAND => {
+                violation(ViolationKind::AlreadyConfigured)
+            }
+            _ if is_launch && self.launched => violation(ViolationKind::AlreadyLaunched),
+            _ => Ok(()),
+        }
+    }
+
+    /// Advances the session by a message sent by either side.
+    ///
+    /// A message that breaks the handshake is reported, and does not advance the
+    /// session.
+    pub fn observe(&mut self, message: &Message) -> Result<(), SessionError> {
+        match message {
+            Message::Request(request) => self.observe_request(request),
+            Message::Response(response) => {
+                self.observe_response(response);
+                Ok(())
+            }
+            Message::Event(event) => self.observe_event(&event.event),
+        }
+    }
+
+    fn observe_request(&mut self, request: &Request) -> Result<(), SessionError> {
+        let command = request.command.as_str();
+        if REVERSE_REQUESTS.contains(&command) {
+            return Ok(());
+        }
+        self.check_request(command)?;
+        match command {
+            requests::Initialize::COMMAND => {
+                self.phase = Phase::Initializing;
+                self.initialize_seq = Some(request.seq);
+            }
+            requests::Launch::COMMAND | requests::Attach::COMMAND => self.launched = true,
+            requests::ConfigurationDone::COMMAND => self.phase = Phase::Running,
+            requests::Disconnect::COMMAND => self.phase = Phase::Disconnected,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn observe_response(&mut self, response: &Response) {
+        if self.phase != Phase::Initializing || self.initialize_seq != Some(response.request_seq) {
+            return;
+        }
+        // A failed `initialize` can be retried.
+        self.phase = if response.success {
+            Phase::WaitingForConfiguration
+        } else {
+            Phase::Uninitialized
+        };
+    }
+
+    fn observe_event(&mut self, event: &Events) -> Result<(), SessionError> {
+        if !matches!(event, Events::Initialized(_)) {
+            return Ok(());
+        }
+        let kind = match self.phase {
+            Phase::WaitingForConfiguration => {
+                self.phase = Phase::Configuring;
+                return Ok(());
+            }
+            Phase::Uninitialized | Phase::Initializing => ViolationKind::InitializedTooEarly,
+            Phase::Configuring | Phase::Running | Phase::Disconnected => {
+                ViolationKind::DuplicateInitialized
+            }
+        };
+        Err(SessionError {
+            phase: self.phase,
+            command: None,
+            kind,
+        })
+    }
+}
+
+impl Default for SessionState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A message that was sent out of order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionError {
+    /// Phase the session was in when the message was sent.
+    pub phase: Phase,
+    /// Command of the offending request, or `None` for the `initialized` event.
+    pub command: Option<String>,
+    pub kind: ViolationKind,
+}
+
+/// The ways in which the handshake order can be broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViolationKind {
+    /// Request sent before the adapter responded to `initialize`.
+    NotInitialized,
+    /// Second `initialize` request.
+    AlreadyInitialized,
+    /// Configuration request sent before the `initialized` event.
+    NotConfigurable,
+    /// Second `configurationDone` request.
+    AlreadyConfigured,
+    /// Second `launch` or `attach` request.
+    AlreadyLaunched,
+    /// Request sent after `disconnect`.
+    Disconnected,
+    /// `initialized` event sent before the `initialize` response.
+    InitializedTooEarly,
+    /// Second `initialized` event.
+    DuplicateInitialized,
+}
+
+impl std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self.kind {
+            ViolationKind::NotInitialized => "sent before the initialize response",
+            ViolationKind::AlreadyInitialized => "session is already initialized",
+            ViolationKind::NotConfigurable => "sent before the initialized event",
+            ViolationKind::AlreadyConfigured => "configuration is already done",
+            ViolationKind::AlreadyLaunched => "session is already launched or attached",
+            ViolationKind::Disconnected => "sent after disconnect",
+            ViolationKind::InitializedTooEarly => "sent before the initialize response",
+            ViolationKind::DuplicateInitialized => "sent more than once",
+        };
+        match &self.command {
+            Some(command) => write!(f, "`{command}` request {message}"),
+            None => write!(f, "`initialized` event {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::Event;
+
+    fn request(seq: u64, command: &str) -> Message {
+        Message::Request(Request {
+            seq,
+            command: command.to_owned(),
+            arguments: Some(serde_json::json!({})),
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+    }
+
+    fn response(request_seq: u64, command: &str, success: bool) -> Message {
+        Message::Response(Response {
+            seq: request_seq,
+            request_seq,
+            success,
+            command: command.to_owned(),
+            body: None,
+            message: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+    }
+
+    fn initialized() -> Message {
+        Message::Event(Box::new(Event::new(1, Events::Initialized(None))))
+    }
+
+    #[test]
+    fn follows_handshake() {
+        let mut session = SessionState::new();
+        assert!(session.allows("initialize"));
+        assert!(!session.allows("threads"));
+
+        session.observe(&request(1, "initialize")).unwrap();
+        assert_eq!(session.phase(), Phase::Initializing);
+        session.observe(&response(1, "initialize", true)).unwrap();
+        assert_eq!(session.phase(), Phase::WaitingForConfiguration);
+        assert!(!session.allows("setBreakpoints"));
+
+        session.observe(&request(2, "launch")).unwrap();
+        session.observe(&initialized()).unwrap();
+        assert_eq!(session.phase(), Phase::Configuring);
+        session.observe(&request(3, "setBreakpoints")).unwrap();
+        session
+            .observe(&request(4, "setExceptionBreakpoints"))
+            .unwrap();
+        session.observe(&request(5, "configurationDone")).unwrap();
+        assert_eq!(session.phase(), Phase::Running);
+        assert!(session.allows("setBreakpoints"));
+        assert!(!session.allows("configurationDone"));
+
+        session.observe(&request(6, "disconnect")).unwrap();
+        assert_eq!(session.phase(), Phase::Disconnected);
+        assert!(!session.allows("threads"));
+    }
+
+    #[test]
+    fn reports_violations() {
+        let mut session = SessionState::new();
+        let error = session.observe(&request(1, "threads")).unwrap_err();
+        assert_eq!(error.kind, ViolationKind::NotInitialized);
+        assert_eq!(
+            error.to_string(),
+            "`threads` request sent before the initialize response"
+        );
+
+        session.observe(&request(2, "initialize")).unwrap();
+        let error = session.observe(&request(3, "launch")).unwrap_err();
+        assert_eq!(error.kind, ViolationKind::NotInitialized);
+        let error = session.observe(&initialized()).unwrap_err();
+        assert_eq!(error.kind, ViolationKind::InitializedTooEarly);
+
+        session.observe(&response(2, "initialize", true)).unwrap();
+        let error = session.observe(&request(4, "initialize")).unwrap_err();
+        assert_eq!(error.kind, ViolationKind::AlreadyInitialized);
+        let error = session.observe(&request(5, "setBreakpoints")).unwrap_err();
+        assert_eq!(error.kind, ViolationKind::NotConfigurable);
+
+        session.observe(&request(6, "attach")).unwrap();
+        let error = session.observe(&request(7, "launch")).unwrap_err();
+        assert_eq!(error.kind, ViolationKind::AlreadyLaunched);
+        session.observe(&initialized()).unwrap();
+        let error = session.observe(&initialized()).unwrap_err();
+        assert_eq!(error.kind, ViolationKind::DuplicateInitialized);
+    }
+
+    #[test]
+    fn failed_initialize_can_be_retried() {
+        let mut session = SessionState::new();
+        session.observe(&request(1, "initialize")).unwrap();
+        session.observe(&response(1, "initialize", false)).unwrap();
+        assert_eq!(session.phase(), Phase::Uninitialized);
+        session.observe(&request(2, "initialize")).unwrap();
+        assert_eq!(session.phase(), Phase::Initializing);
+    }
+}
@@ -0,0 +1,237 @@
+use std::io::{BufRead, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::messages::Message;
+
+/// Which side sent a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Direction {
+    ClientToAdapter,
+    AdapterToClient,
+}
+
+/// A message recorded in a trace.
+///
+/// Traces are JSON lines files with one entry per line, e.g.
+///
+/// ```text
+/// {"direction":"clientToAdapter","timestamp":1700000000000,"message":{"type":"request","seq":1,"command":"threads"}}
+/// {"direction":"adapterToClient","timestamp":1700000000003,"message":{"type":"response","seq":1,"request_seq":1,"success":true,"command":"threads","body":{"threads":[]}}}
+/// ```
+///
+/// `message` is the protocol message as it was sent, which is recorded even if
+/// it is not a valid [`Message`]. Blank lines are ignored.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TraceEntry {
+    pub direction: Direction,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub message: serde_json::Value,
+}
+
+impl TraceEntry {
+    /// Creates an entry for a message that is being sent now.
+    pub fn now(direction: Direction, message: serde_json::Value) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |t| t.as_millis() as u64);
+        TraceEntry {
+            direction,
+            timestamp,
+            message,
+        }
+    }
+
+    /// Decodes the recorded message.
+    pub fn decode(&self) -> Result<Message, serde_json::Error> {
+        Message::deserialize(&self.message)
+    }
+}
+
+/// Reads trace entries from a JSON lines file.
+pub struct TraceReader<R> {
+    reader: R,
+    line_number: usize,
+    line: String,
+}
+
+impl<R: BufRead> TraceReader<R> {
+    pub fn new(reader: R) -> Self {
+        TraceReader {
+            reader,
+            line_number: 0,
+            line: String::new(),
+        }
+    }
+
+    /// Reads the next entry, returning `Ok(None)` at the end of the trace.
+    ///
+    /// An invalid line is returned as an error, and the next call continues
+    /// with the line after it.
+    pub fn read(&mut self) -> Result<Option<TraceEntry>, TraceError> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            self.line_number += 1;
+            if self.line.trim().is_empty() {
+                continue;
+            }
+            return serde_json::from_str(&self.line).map(Some).map_err(|error| {
+                TraceError::Invalid {
+                    line: self.line_number,
+                    error,
+                }
+            });
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for TraceReader<R> {
+    type Item = Result<TraceEntry, TraceError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
+
+/// Writes trace entries as JSON lines.
+pub struct TraceWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> TraceWriter<W> {
+    pub fn new(writer: W) -> Self {
+        TraceWriter { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes a single entry and flushes the underlying writer, so that the
+    /// trace is complete even if the process is killed.
+    pub fn write(&mut self, entry: &TraceEntry) -> Result<(), TraceError> {
+        let mut line = serde_json::to_vec(entry).map_err(TraceError::Encode)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Reads a whole trace, with an error in place of each invalid line.
+///
+/// Reading stops early only if the underlying reader fails.
+pub fn read_trace(reader: impl BufRead) -> Vec<Result<TraceEntry, TraceError>> {
+    let mut entries = Vec::new();
+    for entry in TraceReader::new(reader) {
+        let failed = matches!(entry, Err(TraceError::Io(_)));
+        entries.push(entry);
+        if failed {
+            break;
+        }
+    }
+    entries
+}
+
+/// Errors that can occur while reading or writing a trace.
+#[derive(Debug)]
+pub enum TraceError {
+    Io(std::io::Error),
+    /// Entry could not be encoded.
+    Encode(serde_json::Error),
+    /// Line is not a valid trace entry. Line numbers start from 1.
+    Invalid {
+        line: usize,
+        error: serde_json::Error,
+    },
+}
+
+impl std::fmt::Display for TraceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceError::Io(e) => write!(f, "io error: {e}"),
+            TraceError::Encode(e) => write!(f, "cannot encode trace entry: {e}"),
+            TraceError::Invalid { line, error } => {
+                write!(f, "invalid trace entry on line {line}: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TraceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TraceError::Io(e) => Some(e),
+            TraceError::Encode(e) => Some(e),
+            TraceError::Invalid { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for TraceError {
+    fn from(e: std::io::Error) -> Self {
+        TraceError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::Request;
+    use serde_json::json;
+
+    #[test]
+    fn round_trip() {
+        let entry = TraceEntry {
+            direction: Direction::ClientToAdapter,
+            timestamp: 1700000000000,
+            message: json!({ "type": "request", "seq": 1, "command": "pause", "arguments": { "threadId": 1 } }),
+        };
+        let mut writer = TraceWriter::new(Vec::new());
+        writer.write(&entry).unwrap();
+        writer.write(&entry).unwrap();
+        let bytes = writer.into_inner();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(
+                bytes.split(|b| *b == b'\n').next().unwrap()
+            )
+            .unwrap(),
+            json!({
+                "direction": "clientToAdapter",
+                "timestamp": 1700000000000u64,
+                "message": { "type": "request", "seq": 1, "command": "pause", "arguments": { "threadId": 1 } },
+            })
+        );
+        let entries = read_trace(&bytes[..]);
+        assert_eq!(entries.len(), 2);
+        for read in entries {
+            assert_eq!(read.unwrap(), entry);
+        }
+        assert!(matches!(
+            entry.decode().unwrap(),
+            Message::Request(Request { seq: 1, ref command, .. }) if command == "pause"
+        ));
+    }
+
+    #[test]
+    fn reports_invalid_lines() {
+        let trace = concat!(
+            "\n",
+            "{\"direction\":\"clientToAdapter\"}\n",
+            "{\"direction\":\"adapterToClient\",\"timestamp\":0,\"message\":{\"type\":\"event\"}}\n",
+        );
+        let entries = read_trace(trace.as_bytes());
+        assert!(matches!(
+            entries[..],
+            [Err(TraceError::Invalid { line: 2, .. }), Ok(_)]
+        ));
+        // Messages that don't decode are still read.
+        assert!(entries[1].as_ref().unwrap().decode().is_err());
+    }
+}
@@ -1,6 +1,8 @@
 // This file is autogenerated. Do not edit by hand.
 // To regenerate from schema, run `cargo run -p generator`.
 
+#![allow(clippy::doc_lazy_continuation)]
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +14,21 @@ pub struct ErrorResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub error: Option<Message>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ErrorResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `cancel` request.
@@ -29,6 +46,21 @@ pub struct CancelArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub progress_id: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for CancelArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The event indicates that the execution of the debuggee has stopped due to some condition.
@@ -74,6 +106,21 @@ pub struct StoppedEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub hit_breakpoint_ids: Option<Vec<i64>>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StoppedEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The reason for the event.
@@ -99,8 +146,13 @@ pub enum StoppedEventReason {
     DataBreakpoint,
     #[serde(rename = "instruction breakpoint")]
     InstructionBreakpoint,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// The event indicates that the execution of the debuggee has continued.
@@ -116,6 +168,21 @@ pub struct ContinuedEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub all_threads_continued: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ContinuedEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The event indicates that the debuggee has exited and returns its exit code.
@@ -124,6 +191,21 @@ pub struct ExitedEvent {
     /// The exit code returned from the debuggee.
     #[serde(rename = "exitCode")]
     pub exit_code: u64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ExitedEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The event indicates that debugging of the debuggee has terminated. This does **not** mean that the debuggee itself has exited.
@@ -135,6 +217,21 @@ pub struct TerminatedEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub restart: Option<serde_json::Value>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for TerminatedEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The event indicates that a thread has started or exited.
@@ -146,6 +243,21 @@ pub struct ThreadEvent {
     /// The identifier of the thread.
     #[serde(rename = "threadId")]
     pub thread_id: i64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ThreadEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The reason for the event.
@@ -156,8 +268,13 @@ pub enum ThreadEventReason {
     Started,
     #[serde(rename = "exited")]
     Exited,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// The event indicates that the target has produced some output.
@@ -212,6 +329,21 @@ pub struct OutputEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub location_reference: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for OutputEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The output category. If not specified or if the category is not understood by the client, `console` is assumed.
@@ -233,8 +365,13 @@ pub enum OutputEventCategory {
     /// Send the output to telemetry instead of showing it to the user.
     #[serde(rename = "telemetry")]
     Telemetry,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// Support for keeping an output log organized by grouping related messages.
@@ -263,6 +400,21 @@ pub struct BreakpointEvent {
     /// The `id` attribute is used to find the target breakpoint, the other attributes are used as the new values.
     #[serde(rename = "breakpoint")]
     pub breakpoint: Breakpoint,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for BreakpointEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The reason for the event.
@@ -275,8 +427,13 @@ pub enum BreakpointEventReason {
     New,
     #[serde(rename = "removed")]
     Removed,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// The event indicates that some information about a module has changed.
@@ -288,6 +445,21 @@ pub struct ModuleEvent {
     /// The new, changed, or removed module. In case of `removed` only the module id is used.
     #[serde(rename = "module")]
     pub module: Module,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ModuleEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The reason for the event.
@@ -310,6 +482,21 @@ pub struct LoadedSourceEvent {
     /// The new, changed, or removed source.
     #[serde(rename = "source")]
     pub source: Source,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for LoadedSourceEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The reason for the event.
@@ -349,6 +536,21 @@ pub struct ProcessEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub pointer_size: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ProcessEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Describes how the debug engine started debugging this process.
@@ -374,12 +576,27 @@ pub struct CapabilitiesEvent {
     /// The set of updated capabilities.
     #[serde(rename = "capabilities")]
     pub capabilities: Capabilities,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for CapabilitiesEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The event signals that a long running operation is about to start and provides additional information for the client to set up a corresponding progress and cancellation UI.
 /// The client is free to delay the showing of the UI in order to reduce flicker.
 /// This event should only be sent if the corresponding capability `supportsProgressReporting` is true.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ProgressStartEvent {
     /// An ID that can be used in subsequent `progressUpdate` and `progressEnd` events to make them refer to the same progress reporting.
     /// IDs must be unique within a debug session.
@@ -410,13 +627,28 @@ pub struct ProgressStartEvent {
     #[serde(rename = "percentage")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    pub percentage: Option<u64>,
+    pub percentage: Option<f64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ProgressStartEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The event signals that the progress reporting needs to be updated with a new message and/or percentage.
 /// The client does not have to update the UI immediately, but the clients needs to keep track of the message and/or percentage values.
 /// This event should only be sent if the corresponding capability `supportsProgressReporting` is true.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ProgressUpdateEvent {
     /// The ID that was introduced in the initial `progressStart` event.
     #[serde(rename = "progressId")]
@@ -430,7 +662,22 @@ pub struct ProgressUpdateEvent {
     #[serde(rename = "percentage")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    pub percentage: Option<u64>,
+    pub percentage: Option<f64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ProgressUpdateEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The event signals the end of the progress reporting with a final message.
@@ -445,6 +692,21 @@ pub struct ProgressEndEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub message: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ProgressEndEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// This event signals that some state in the debug adapter has changed and requires that the client needs to re-render the data snapshot previously requested.
@@ -467,6 +729,21 @@ pub struct InvalidatedEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub stack_frame_id: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for InvalidatedEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// This event indicates that some memory range has been updated. It should only be sent if the corresponding capability `supportsMemoryEvent` is true.
@@ -483,6 +760,21 @@ pub struct MemoryEvent {
     /// Number of bytes updated.
     #[serde(rename = "count")]
     pub count: u64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for MemoryEvent {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `runInTerminal` request.
@@ -515,6 +807,21 @@ pub struct RunInTerminalRequestArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub args_can_be_interpreted_by_shell: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for RunInTerminalRequestArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// What kind of terminal to launch. Defaults to `integrated` if not specified.
@@ -539,6 +846,21 @@ pub struct RunInTerminalResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub shell_process_id: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for RunInTerminalResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `startDebugging` request.
@@ -550,6 +872,21 @@ pub struct StartDebuggingRequestArguments {
     /// Indicates whether the new debug session should be started with a `launch` or `attach` request.
     #[serde(rename = "request")]
     pub request: StartDebuggingRequestArgumentsRequest,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StartDebuggingRequestArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Indicates whether the new debug session should be started with a `launch` or `attach` request.
@@ -647,6 +984,21 @@ pub struct InitializeRequestArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub supports_ansistyling: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for InitializeRequestArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Determines in what format paths are specified. The default is `path`, which is the native format.
@@ -657,13 +1009,34 @@ pub enum InitializeRequestArgumentsPathFormat {
     Path,
     #[serde(rename = "uri")]
     Uri,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// Arguments for `configurationDone` request.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
-pub struct ConfigurationDoneArguments {}
+pub struct ConfigurationDoneArguments {
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ConfigurationDoneArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
+}
 
 /// Arguments for `disconnect` request.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
@@ -687,6 +1060,21 @@ pub struct DisconnectArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub suspend_debuggee: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for DisconnectArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `terminate` request.
@@ -697,6 +1085,21 @@ pub struct TerminateArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub restart: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for TerminateArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `breakpointLocations` request.
@@ -723,6 +1126,21 @@ pub struct BreakpointLocationsArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub end_column: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for BreakpointLocationsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `breakpointLocations` request.
@@ -732,6 +1150,21 @@ pub struct BreakpointLocationsResponse {
     /// Sorted set of possible breakpoint locations.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<BreakpointLocation>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for BreakpointLocationsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `setBreakpoints` request.
@@ -755,6 +1188,21 @@ pub struct SetBreakpointsArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub source_modified: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetBreakpointsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `setBreakpoints` request.
@@ -768,6 +1216,21 @@ pub struct SetBreakpointsResponse {
     /// The array elements are in the same order as the elements of the `breakpoints` (or the deprecated `lines`) array in the arguments.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<Breakpoint>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetBreakpointsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `setFunctionBreakpoints` request.
@@ -776,6 +1239,21 @@ pub struct SetFunctionBreakpointsArguments {
     /// The function names of the breakpoints.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<FunctionBreakpoint>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetFunctionBreakpointsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `setFunctionBreakpoints` request.
@@ -785,6 +1263,21 @@ pub struct SetFunctionBreakpointsResponse {
     /// Information about the breakpoints. The array elements correspond to the elements of the `breakpoints` array.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<Breakpoint>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetFunctionBreakpointsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `setExceptionBreakpoints` request.
@@ -804,6 +1297,21 @@ pub struct SetExceptionBreakpointsArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub exception_options: Option<Vec<ExceptionOptions>>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetExceptionBreakpointsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `setExceptionBreakpoints` request.
@@ -818,6 +1326,21 @@ pub struct SetExceptionBreakpointsResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub breakpoints: Option<Vec<Breakpoint>>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetExceptionBreakpointsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `dataBreakpointInfo` request.
@@ -857,6 +1380,21 @@ pub struct DataBreakpointInfoArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub mode: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for DataBreakpointInfoArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `dataBreakpointInfo` request.
@@ -878,6 +1416,21 @@ pub struct DataBreakpointInfoResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub can_persist: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for DataBreakpointInfoResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `setDataBreakpoints` request.
@@ -886,6 +1439,21 @@ pub struct SetDataBreakpointsArguments {
     /// The contents of this array replaces all existing data breakpoints. An empty array clears all data breakpoints.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<DataBreakpoint>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetDataBreakpointsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `setDataBreakpoints` request.
@@ -895,6 +1463,21 @@ pub struct SetDataBreakpointsResponse {
     /// Information about the data breakpoints. The array elements correspond to the elements of the input argument `breakpoints` array.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<Breakpoint>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetDataBreakpointsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `setInstructionBreakpoints` request
@@ -903,6 +1486,21 @@ pub struct SetInstructionBreakpointsArguments {
     /// The instruction references of the breakpoints
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<InstructionBreakpoint>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetInstructionBreakpointsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `setInstructionBreakpoints` request
@@ -911,6 +1509,21 @@ pub struct SetInstructionBreakpointsResponse {
     /// Information about the breakpoints. The array elements correspond to the elements of the `breakpoints` array.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<Breakpoint>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetInstructionBreakpointsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `continue` request.
@@ -924,6 +1537,21 @@ pub struct ContinueArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub single_thread: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ContinueArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `continue` request.
@@ -934,6 +1562,21 @@ pub struct ContinueResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub all_threads_continued: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ContinueResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `next` request.
@@ -952,6 +1595,21 @@ pub struct NextArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub granularity: Option<SteppingGranularity>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for NextArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `stepIn` request.
@@ -975,6 +1633,21 @@ pub struct StepInArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub granularity: Option<SteppingGranularity>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StepInArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `stepOut` request.
@@ -993,6 +1666,21 @@ pub struct StepOutArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub granularity: Option<SteppingGranularity>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StepOutArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `stepBack` request.
@@ -1011,6 +1699,21 @@ pub struct StepBackArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub granularity: Option<SteppingGranularity>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StepBackArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `reverseContinue` request.
@@ -1024,6 +1727,21 @@ pub struct ReverseContinueArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub single_thread: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ReverseContinueArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `restartFrame` request.
@@ -1032,6 +1750,21 @@ pub struct RestartFrameArguments {
     /// Restart the stack frame identified by `frameId`. The `frameId` must have been obtained in the current suspended state. See 'Lifetime of Object References' in the Overview section for details.
     #[serde(rename = "frameId")]
     pub frame_id: u64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for RestartFrameArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `goto` request.
@@ -1043,6 +1776,21 @@ pub struct GotoArguments {
     /// The location where the debuggee will continue to run.
     #[serde(rename = "targetId")]
     pub target_id: u64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for GotoArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `pause` request.
@@ -1051,6 +1799,21 @@ pub struct PauseArguments {
     /// Pause execution for this thread.
     #[serde(rename = "threadId")]
     pub thread_id: i64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for PauseArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `stackTrace` request.
@@ -1075,6 +1838,21 @@ pub struct StackTraceArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub format: Option<StackFrameFormat>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StackTraceArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `stackTrace` request.
@@ -1089,6 +1867,21 @@ pub struct StackTraceResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub total_frames: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StackTraceResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `scopes` request.
@@ -1097,6 +1890,21 @@ pub struct ScopesArguments {
     /// Retrieve the scopes for the stack frame identified by `frameId`. The `frameId` must have been obtained in the current suspended state. See 'Lifetime of Object References' in the Overview section for details.
     #[serde(rename = "frameId")]
     pub frame_id: u64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ScopesArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `scopes` request.
@@ -1105,6 +1913,21 @@ pub struct ScopesResponse {
     /// The scopes of the stack frame. If the array has length zero, there are no scopes available.
     #[serde(rename = "scopes")]
     pub scopes: Vec<Scope>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ScopesResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `variables` request.
@@ -1136,6 +1959,21 @@ pub struct VariablesArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub format: Option<ValueFormat>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for VariablesArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Filter to limit the child variables to either named or indexed. If omitted, both types are fetched.
@@ -1153,6 +1991,21 @@ pub struct VariablesResponse {
     /// All (or a range) of variables for the given variable reference.
     #[serde(rename = "variables")]
     pub variables: Vec<Variable>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for VariablesResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `setVariable` request.
@@ -1172,6 +2025,21 @@ pub struct SetVariableArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub format: Option<ValueFormat>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetVariableArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `setVariable` request.
@@ -1220,6 +2088,21 @@ pub struct SetVariableResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub value_location_reference: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetVariableResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `source` request.
@@ -1234,6 +2117,21 @@ pub struct SourceArguments {
     /// This is provided for backward compatibility since old clients do not understand the `source` attribute.
     #[serde(rename = "sourceReference")]
     pub source_reference: u64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SourceArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `source` request.
@@ -1247,6 +2145,21 @@ pub struct SourceResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub mime_type: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SourceResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `threads` request.
@@ -1255,6 +2168,21 @@ pub struct ThreadsResponse {
     /// All threads.
     #[serde(rename = "threads")]
     pub threads: Vec<Thread>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ThreadsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `terminateThreads` request.
@@ -1265,6 +2193,21 @@ pub struct TerminateThreadsArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub thread_ids: Option<Vec<i64>>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for TerminateThreadsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `modules` request.
@@ -1280,6 +2223,21 @@ pub struct ModulesArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub module_count: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ModulesArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `modules` request.
@@ -1293,11 +2251,42 @@ pub struct ModulesResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub total_modules: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ModulesResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `loadedSources` request.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
-pub struct LoadedSourcesArguments {}
+pub struct LoadedSourcesArguments {
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for LoadedSourcesArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
+}
 
 /// Response to `loadedSources` request.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
@@ -1305,6 +2294,21 @@ pub struct LoadedSourcesResponse {
     /// Set of loaded sources.
     #[serde(rename = "sources")]
     pub sources: Vec<Source>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for LoadedSourcesResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `evaluate` request.
@@ -1346,6 +2350,21 @@ pub struct EvaluateArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub format: Option<ValueFormat>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for EvaluateArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The context in which the evaluate request is used.
@@ -1369,8 +2388,13 @@ pub enum EvaluateArgumentsContext {
     /// evaluate is called from a variables view context.
     #[serde(rename = "variables")]
     Variables,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// Response to `evaluate` request.
@@ -1421,6 +2445,21 @@ pub struct EvaluateResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub value_location_reference: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for EvaluateResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `setExpression` request.
@@ -1442,6 +2481,21 @@ pub struct SetExpressionArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub format: Option<ValueFormat>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetExpressionArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `setExpression` request.
@@ -1494,6 +2548,21 @@ pub struct SetExpressionResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub value_location_reference: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SetExpressionResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `stepInTargets` request.
@@ -1502,6 +2571,21 @@ pub struct StepInTargetsArguments {
     /// The stack frame for which to retrieve the possible step-in targets.
     #[serde(rename = "frameId")]
     pub frame_id: u64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StepInTargetsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `stepInTargets` request.
@@ -1510,6 +2594,21 @@ pub struct StepInTargetsResponse {
     /// The possible step-in targets of the specified source location.
     #[serde(rename = "targets")]
     pub targets: Vec<StepInTarget>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StepInTargetsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `gotoTargets` request.
@@ -1526,6 +2625,21 @@ pub struct GotoTargetsArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub column: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for GotoTargetsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `gotoTargets` request.
@@ -1534,6 +2648,21 @@ pub struct GotoTargetsResponse {
     /// The possible goto targets of the specified location.
     #[serde(rename = "targets")]
     pub targets: Vec<GotoTarget>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for GotoTargetsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `completions` request.
@@ -1555,6 +2684,21 @@ pub struct CompletionsArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub line: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for CompletionsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `completions` request.
@@ -1563,6 +2707,21 @@ pub struct CompletionsResponse {
     /// The possible completions for .
     #[serde(rename = "targets")]
     pub targets: Vec<CompletionItem>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for CompletionsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `exceptionInfo` request.
@@ -1571,6 +2730,21 @@ pub struct ExceptionInfoArguments {
     /// Thread for which exception information should be retrieved.
     #[serde(rename = "threadId")]
     pub thread_id: i64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ExceptionInfoArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `exceptionInfo` request.
@@ -1592,6 +2766,21 @@ pub struct ExceptionInfoResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub details: Option<ExceptionDetails>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ExceptionInfoResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `readMemory` request.
@@ -1608,6 +2797,21 @@ pub struct ReadMemoryArguments {
     /// Number of bytes to read at the specified location and offset.
     #[serde(rename = "count")]
     pub count: u64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ReadMemoryArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `readMemory` request.
@@ -1628,6 +2832,21 @@ pub struct ReadMemoryResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub data: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ReadMemoryResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `writeMemory` request.
@@ -1650,6 +2869,21 @@ pub struct WriteMemoryArguments {
     /// Bytes to write, encoded using base64.
     #[serde(rename = "data")]
     pub data: String,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for WriteMemoryArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `writeMemory` request.
@@ -1665,6 +2899,21 @@ pub struct WriteMemoryResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub bytes_written: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for WriteMemoryResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `disassemble` request.
@@ -1692,6 +2941,21 @@ pub struct DisassembleArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub resolve_symbols: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for DisassembleArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `disassemble` request.
@@ -1700,6 +2964,21 @@ pub struct DisassembleResponse {
     /// The list of disassembled instructions.
     #[serde(rename = "instructions")]
     pub instructions: Vec<DisassembledInstruction>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for DisassembleResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Arguments for `locations` request.
@@ -1708,6 +2987,21 @@ pub struct LocationsArguments {
     /// Location reference to resolve.
     #[serde(rename = "locationReference")]
     pub location_reference: u64,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for LocationsArguments {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Response to `locations` request.
@@ -1734,6 +3028,21 @@ pub struct LocationsResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub end_column: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for LocationsResponse {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Information about the capabilities of a debug adapter.
@@ -1951,6 +3260,121 @@ pub struct Capabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub supports_ansistyling: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for Capabilities {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
+}
+
+impl Capabilities {
+    /// Returns a boolean field by its protocol name, e.g. `supportsStepBack`.
+    ///
+    /// Returns `None` if the field is not set or there is no boolean field with that name.
+    pub fn flag(&self, name: &str) -> Option<bool> {
+        match name {
+            "supportsConfigurationDoneRequest" => self.supports_configuration_done_request,
+            "supportsFunctionBreakpoints" => self.supports_function_breakpoints,
+            "supportsConditionalBreakpoints" => self.supports_conditional_breakpoints,
+            "supportsHitConditionalBreakpoints" => self.supports_hit_conditional_breakpoints,
+            "supportsEvaluateForHovers" => self.supports_evaluate_for_hovers,
+            "supportsStepBack" => self.supports_step_back,
+            "supportsSetVariable" => self.supports_set_variable,
+            "supportsRestartFrame" => self.supports_restart_frame,
+            "supportsGotoTargetsRequest" => self.supports_goto_targets_request,
+            "supportsStepInTargetsRequest" => self.supports_step_in_targets_request,
+            "supportsCompletionsRequest" => self.supports_completions_request,
+            "supportsModulesRequest" => self.supports_modules_request,
+            "supportsRestartRequest" => self.supports_restart_request,
+            "supportsExceptionOptions" => self.supports_exception_options,
+            "supportsValueFormattingOptions" => self.supports_value_formatting_options,
+            "supportsExceptionInfoRequest" => self.supports_exception_info_request,
+            "supportTerminateDebuggee" => self.support_terminate_debuggee,
+            "supportSuspendDebuggee" => self.support_suspend_debuggee,
+            "supportsDelayedStackTraceLoading" => self.supports_delayed_stack_trace_loading,
+            "supportsLoadedSourcesRequest" => self.supports_loaded_sources_request,
+            "supportsLogPoints" => self.supports_log_points,
+            "supportsTerminateThreadsRequest" => self.supports_terminate_threads_request,
+            "supportsSetExpression" => self.supports_set_expression,
+            "supportsTerminateRequest" => self.supports_terminate_request,
+            "supportsDataBreakpoints" => self.supports_data_breakpoints,
+            "supportsReadMemoryRequest" => self.supports_read_memory_request,
+            "supportsWriteMemoryRequest" => self.supports_write_memory_request,
+            "supportsDisassembleRequest" => self.supports_disassemble_request,
+            "supportsCancelRequest" => self.supports_cancel_request,
+            "supportsBreakpointLocationsRequest" => self.supports_breakpoint_locations_request,
+            "supportsClipboardContext" => self.supports_clipboard_context,
+            "supportsSteppingGranularity" => self.supports_stepping_granularity,
+            "supportsInstructionBreakpoints" => self.supports_instruction_breakpoints,
+            "supportsExceptionFilterOptions" => self.supports_exception_filter_options,
+            "supportsSingleThreadExecutionRequests" => {
+                self.supports_single_thread_execution_requests
+            }
+            "supportsDataBreakpointBytes" => self.supports_data_breakpoint_bytes,
+            "supportsANSIStyling" => self.supports_ansistyling,
+            _ => None,
+        }
+    }
+
+    /// Sets a boolean field by its protocol name.
+    ///
+    /// Returns false if there is no boolean field with that name.
+    pub fn set_flag(&mut self, name: &str, value: Option<bool>) -> bool {
+        let flag = match name {
+            "supportsConfigurationDoneRequest" => &mut self.supports_configuration_done_request,
+            "supportsFunctionBreakpoints" => &mut self.supports_function_breakpoints,
+            "supportsConditionalBreakpoints" => &mut self.supports_conditional_breakpoints,
+            "supportsHitConditionalBreakpoints" => &mut self.supports_hit_conditional_breakpoints,
+            "supportsEvaluateForHovers" => &mut self.supports_evaluate_for_hovers,
+            "supportsStepBack" => &mut self.supports_step_back,
+            "supportsSetVariable" => &mut self.supports_set_variable,
+            "supportsRestartFrame" => &mut self.supports_restart_frame,
+            "supportsGotoTargetsRequest" => &mut self.supports_goto_targets_request,
+            "supportsStepInTargetsRequest" => &mut self.supports_step_in_targets_request,
+            "supportsCompletionsRequest" => &mut self.supports_completions_request,
+            "supportsModulesRequest" => &mut self.supports_modules_request,
+            "supportsRestartRequest" => &mut self.supports_restart_request,
+            "supportsExceptionOptions" => &mut self.supports_exception_options,
+            "supportsValueFormattingOptions" => &mut self.supports_value_formatting_options,
+            "supportsExceptionInfoRequest" => &mut self.supports_exception_info_request,
+            "supportTerminateDebuggee" => &mut self.support_terminate_debuggee,
+            "supportSuspendDebuggee" => &mut self.support_suspend_debuggee,
+            "supportsDelayedStackTraceLoading" => &mut self.supports_delayed_stack_trace_loading,
+            "supportsLoadedSourcesRequest" => &mut self.supports_loaded_sources_request,
+            "supportsLogPoints" => &mut self.supports_log_points,
+            "supportsTerminateThreadsRequest" => &mut self.supports_terminate_threads_request,
+            "supportsSetExpression" => &mut self.supports_set_expression,
+            "supportsTerminateRequest" => &mut self.supports_terminate_request,
+            "supportsDataBreakpoints" => &mut self.supports_data_breakpoints,
+            "supportsReadMemoryRequest" => &mut self.supports_read_memory_request,
+            "supportsWriteMemoryRequest" => &mut self.supports_write_memory_request,
+            "supportsDisassembleRequest" => &mut self.supports_disassemble_request,
+            "supportsCancelRequest" => &mut self.supports_cancel_request,
+            "supportsBreakpointLocationsRequest" => &mut self.supports_breakpoint_locations_request,
+            "supportsClipboardContext" => &mut self.supports_clipboard_context,
+            "supportsSteppingGranularity" => &mut self.supports_stepping_granularity,
+            "supportsInstructionBreakpoints" => &mut self.supports_instruction_breakpoints,
+            "supportsExceptionFilterOptions" => &mut self.supports_exception_filter_options,
+            "supportsSingleThreadExecutionRequests" => {
+                &mut self.supports_single_thread_execution_requests
+            }
+            "supportsDataBreakpointBytes" => &mut self.supports_data_breakpoint_bytes,
+            "supportsANSIStyling" => &mut self.supports_ansistyling,
+            _ => return false,
+        };
+        *flag = value;
+        true
+    }
 }
 
 /// An `ExceptionBreakpointsFilter` is shown in the UI as an filter option for configuring how exceptions are dealt with.
@@ -1982,6 +3406,21 @@ pub struct ExceptionBreakpointsFilter {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub condition_description: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ExceptionBreakpointsFilter {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A structured message object. Used to return errors from requests.
@@ -2019,6 +3458,21 @@ pub struct Message {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub url_label: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for Message {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A Module object represents a row in the modules view.
@@ -2076,6 +3530,21 @@ pub struct Module {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub address_range: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for Module {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A `ColumnDescriptor` specifies what module attribute to show in a column of the modules view, how to format it,
@@ -2104,6 +3573,21 @@ pub struct ColumnDescriptor {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub width: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ColumnDescriptor {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Datatype of values in this column. Defaults to `string` if not specified.
@@ -2129,6 +3613,21 @@ pub struct Thread {
     #[serde(rename = "name")]
     #[serde(default)]
     pub name: String,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for Thread {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A `Source` is a descriptor for source code.
@@ -2181,11 +3680,27 @@ pub struct Source {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub checksums: Option<Vec<Checksum>>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for Source {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A hint for how to present the source in the UI.
 /// A value of `deemphasize` can be used to indicate that the source is not available or that it is skipped on stepping.
-#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Deserialize, Serialize, JsonSchema)]
+#[cfg_attr(not(feature = "extra-fields"), derive(Copy))]
 pub enum SourcePresentationHint {
     #[serde(rename = "normal")]
     Normal,
@@ -2193,8 +3708,13 @@ pub enum SourcePresentationHint {
     Emphasize,
     #[serde(rename = "deemphasize")]
     Deemphasize,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// A Stackframe contains the source location.
@@ -2213,7 +3733,7 @@ pub struct StackFrame {
     #[serde(default)]
     pub source: Option<Source>,
     /// The line within the source of the frame. If the source attribute is missing or doesn't exist, `line` is 0 and should be ignored by the client.
-    #[serde(rename = "line", deserialize_with = "negative_to_zero")]
+    #[serde(rename = "line", deserialize_with = "crate::negative_to_zero")]
     pub line: u64,
     /// Start position of the range covered by the stack frame. It is measured in UTF-16 code units and the client capability `columnsStartAt1` determines whether it is 0- or 1-based. If attribute `source` is missing or doesn't exist, `column` is 0 and should be ignored by the client.
     #[serde(rename = "column")]
@@ -2249,11 +3769,27 @@ pub struct StackFrame {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub presentation_hint: Option<StackFramePresentationHint>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StackFrame {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A hint for how to present this frame in the UI.
 /// A value of `label` can be used to indicate that the frame is an artificial frame that is used as a visual label or separator. A value of `subtle` can be used to change the appearance of a frame in a 'subtle' way.
-#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Deserialize, Serialize, JsonSchema)]
+#[cfg_attr(not(feature = "extra-fields"), derive(Copy))]
 pub enum StackFramePresentationHint {
     #[serde(rename = "normal")]
     Normal,
@@ -2263,8 +3799,13 @@ pub enum StackFramePresentationHint {
     Subtle,
     #[serde(rename = "deemphasize")]
     Deemphasize,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// A `Scope` is a named container for variables. Optionally a scope can map to a source or a range within a source.
@@ -2321,6 +3862,21 @@ pub struct Scope {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub end_column: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for Scope {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A hint for how to present this scope in the UI. If this attribute is missing, the scope is shown with a generic UI.
@@ -2339,8 +3895,13 @@ pub enum ScopePresentationHint {
     /// Scope contains one or more return values.
     #[serde(rename = "returnValue")]
     ReturnValue,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// A Variable is a name/value pair.
@@ -2413,6 +3974,21 @@ pub struct Variable {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub value_location_reference: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for Variable {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Properties of a variable that can be used to determine how to render the variable in the UI.
@@ -2440,6 +4016,21 @@ pub struct VariablePresentationHint {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub lazy: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for VariablePresentationHint {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// The kind of variable. Before introducing additional values, try to use the listed values.
@@ -2479,8 +4070,13 @@ pub enum VariablePresentationHintKind {
     /// Deprecated: Indicates that a data breakpoint is registered for the object. The `hasDataBreakpoint` attribute should generally be used instead.
     #[serde(rename = "dataBreakpoint")]
     DataBreakpoint,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 #[derive(PartialEq, Eq, Debug, Hash, Clone, Deserialize, Serialize)]
@@ -2510,8 +4106,13 @@ pub enum VariablePresentationHintAttributes {
     /// Indicates that the object has its value tracked by a data breakpoint.
     #[serde(rename = "hasDataBreakpoint")]
     HasDataBreakpoint,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// Visibility of variable. Before introducing additional values, try to use the listed values.
@@ -2528,8 +4129,13 @@ pub enum VariablePresentationHintVisibility {
     Internal,
     #[serde(rename = "final")]
     Final,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// Properties of a breakpoint location returned from the `breakpointLocations` request.
@@ -2553,6 +4159,21 @@ pub struct BreakpointLocation {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub end_column: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for BreakpointLocation {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Properties of a breakpoint or logpoint passed to the `setBreakpoints` request.
@@ -2593,6 +4214,21 @@ pub struct SourceBreakpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub mode: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for SourceBreakpoint {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Properties of a breakpoint passed to the `setFunctionBreakpoints` request.
@@ -2614,6 +4250,21 @@ pub struct FunctionBreakpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub hit_condition: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for FunctionBreakpoint {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// This enumeration defines all possible access types for data breakpoints.
@@ -2649,6 +4300,21 @@ pub struct DataBreakpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub hit_condition: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for DataBreakpoint {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Properties of a breakpoint passed to the `setInstructionBreakpoints` request
@@ -2682,6 +4348,21 @@ pub struct InstructionBreakpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub mode: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for InstructionBreakpoint {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Information about a breakpoint created in `setBreakpoints`, `setFunctionBreakpoints`, `setInstructionBreakpoints`, or `setDataBreakpoints` requests.
@@ -2746,6 +4427,21 @@ pub struct Breakpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub reason: Option<BreakpointReason>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for Breakpoint {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A machine-readable explanation of why a breakpoint may not be verified. If a breakpoint is verified or a specific reason is not known, the adapter should omit this property. Possible values include:
@@ -2805,6 +4501,21 @@ pub struct StepInTarget {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub end_column: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StepInTarget {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A `GotoTarget` describes a code location that can be used as a target in the `goto` request.
@@ -2840,6 +4551,21 @@ pub struct GotoTarget {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub instruction_pointer_reference: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for GotoTarget {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// `CompletionItems` are the suggestions returned from the `completions` request.
@@ -2888,6 +4614,21 @@ pub struct CompletionItem {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub selection_length: Option<u64>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for CompletionItem {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Some predefined types for the CompletionItem. Please note that not all clients have specific icons for all of them.
@@ -2955,6 +4696,21 @@ pub struct Checksum {
     /// Value of the checksum, encoded as a hexadecimal value.
     #[serde(rename = "checksum")]
     pub checksum: String,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for Checksum {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Provides formatting information for a value.
@@ -2965,6 +4721,21 @@ pub struct ValueFormat {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub hex: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ValueFormat {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Provides formatting information for a stack frame.
@@ -3010,6 +4781,21 @@ pub struct StackFrameFormat {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub include_all: Option<bool>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for StackFrameFormat {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// An `ExceptionFilterOptions` is used to specify an exception filter together with a condition for the `setExceptionBreakpoints` request.
@@ -3029,6 +4815,21 @@ pub struct ExceptionFilterOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub mode: Option<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ExceptionFilterOptions {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// An `ExceptionOptions` assigns configuration options to a set of exceptions.
@@ -3043,6 +4844,21 @@ pub struct ExceptionOptions {
     /// Condition when a thrown exception should result in a break.
     #[serde(rename = "breakMode")]
     pub break_mode: ExceptionBreakMode,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ExceptionOptions {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// This enumeration defines all possible conditions when a thrown exception should result in a break.
@@ -3074,6 +4890,21 @@ pub struct ExceptionPathSegment {
     /// Depending on the value of `negate` the names that should match or not match.
     #[serde(rename = "names")]
     pub names: Vec<String>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ExceptionPathSegment {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Detailed information about an exception that has occurred.
@@ -3109,6 +4940,21 @@ pub struct ExceptionDetails {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub inner_exception: Option<Vec<ExceptionDetails>>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ExceptionDetails {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Represents a single disassembled instruction.
@@ -3164,19 +5010,40 @@ pub struct DisassembledInstruction {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub presentation_hint: Option<DisassembledInstructionPresentationHint>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for DisassembledInstruction {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// A hint for how to present the instruction in the UI.
 ///
 /// A value of `invalid` may be used to indicate this instruction is 'filler' and cannot be reached by the program. For example, unreadable memory addresses may be presented is 'invalid.'
-#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Deserialize, Serialize, JsonSchema)]
+#[cfg_attr(not(feature = "extra-fields"), derive(Copy))]
 pub enum DisassembledInstructionPresentationHint {
     #[serde(rename = "normal")]
     Normal,
     #[serde(rename = "invalid")]
     Invalid,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// Logical areas that can be invalidated by the `invalidated` event.
@@ -3195,8 +5062,13 @@ pub enum InvalidatedAreas {
     /// Previously fetched variable data has become invalid and needs to be refetched.
     #[serde(rename = "variables")]
     Variables,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// A `BreakpointMode` is provided as a option when setting breakpoints on sources or instructions.
@@ -3216,6 +5088,21 @@ pub struct BreakpointMode {
     /// Describes one or more type of breakpoint this mode applies to.
     #[serde(rename = "appliesTo")]
     pub applies_to: Vec<BreakpointModeApplicability>,
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for BreakpointMode {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
 }
 
 /// Describes one or more type of breakpoint a `BreakpointMode` applies to. This is a non-exhaustive enumeration and may expand as future breakpoint types are added.
@@ -3234,8 +5121,13 @@ pub enum BreakpointModeApplicability {
     /// In `InstructionBreakpoint`s
     #[serde(rename = "instruction")]
     Instruction,
+    #[cfg(not(feature = "extra-fields"))]
     #[serde(other)]
     Unknown,
+    /// A value not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 #[derive(PartialEq, Eq, Debug, Hash, Clone, Deserialize, Serialize)]
@@ -3264,18 +5156,21 @@ pub struct RestartArguments {
 }
 
 /// Arguments for `Threads` request.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
-pub struct ThreadsArgument {}
-
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ThreadsArgument {
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = "extra-fields")]
+impl crate::extension::Extensible for ThreadsArgument {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
 
-fn negative_to_zero<'de, D>(deserializer: D) -> Result<u64, D::Error>
-where
-    D: serde::de::Deserializer<'de>,
-{
-    let v = i64::deserialize(deserializer)?;
-    if v < 0 {
-        Ok(0)
-    } else {
-        Ok(v as u64)
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
     }
 }
@@ -0,0 +1,10 @@
+// This file is autogenerated. Do not edit by hand.
+// To regenerate from schema, run `cargo run -p generator`.
+
+#![allow(clippy::doc_lazy_continuation)]
+
+/// Protocol version of the types at the crate root.
+pub const LATEST: &str = "1.65";
+
+/// Protocol versions that types are generated for, from the oldest to the latest.
+pub const VERSIONS: &[&str] = &["1.65"];
@@ -0,0 +1,391 @@
+use indexmap::IndexMap;
+
+use crate::{
+    collect_events, collect_requests, to_pascal_case, to_snake_case, Enum, Object, ProtocolType,
+    Target, Type,
+};
+
+/// A difference between two schemas, in terms of the generated Rust types.
+pub struct Change {
+    /// Whether code using the `dap-types` API generated from the old schema can
+    /// stop compiling with the new one.
+    pub breaking: bool,
+    pub description: String,
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = if self.breaking {
+            "breaking"
+        } else {
+            "compatible"
+        };
+        write!(f, "{kind}: {}", self.description)
+    }
+}
+
+/// Compares the types generated from two schemas.
+pub fn diff(old: &[ProtocolType], new: &[ProtocolType]) -> Vec<Change> {
+    let mut diff = Diff::default();
+    let old_types = generated_types(old);
+    let new_types = generated_types(new);
+    for (name, old_ty) in &old_types {
+        match new_types.get(name) {
+            Some(new_ty) => diff.compare_types(name, old_ty, new_ty),
+            None => diff.breaking(format!("removed type `{name}`")),
+        }
+    }
+    for name in new_types.keys() {
+        if !old_types.contains_key(name) {
+            diff.compatible(format!("added type `{name}`"));
+        }
+    }
+
+    let old_requests = collect_requests(old, Target::Latest);
+    let new_requests = collect_requests(new, Target::Latest);
+    for old_request in &old_requests {
+        let command = &old_request.command;
+        let Some(new_request) = new_requests.iter().find(|r| r.command == *command) else {
+            diff.breaking(format!("removed request `{command}`"));
+            continue;
+        };
+        let what = format!("arguments of request `{command}`");
+        diff.compare_bodies(&what, &old_request.arguments, &new_request.arguments);
+        let what = format!("response of request `{command}`");
+        diff.compare_bodies(&what, &old_request.response, &new_request.response);
+    }
+    for new_request in &new_requests {
+        if !old_requests
+            .iter()
+            .any(|r| r.command == new_request.command)
+        {
+            // Adds a variant to `Requests`, which is matched exhaustively.
+            diff.breaking(format!("added request `{}`", new_request.command));
+        }
+    }
+
+    let old_events = collect_events(old, Target::Latest);
+    let new_events = collect_events(new, Target::Latest);
+    for old_event in &old_events {
+        let event = &old_event.event;
+        let Some(new_event) = new_events.iter().find(|e| e.event == *event) else {
+            diff.breaking(format!("removed event `{event}`"));
+            continue;
+        };
+        let what = format!("body of event `{event}`");
+        diff.compare_bodies(&what, &old_event.body, &new_event.body);
+    }
+    for new_event in &new_events {
+        if !old_events.iter().any(|e| e.event == new_event.event) {
+            // Adds a variant to `Events`, which is matched exhaustively.
+            diff.breaking(format!("added event `{}`", new_event.event));
+        }
+    }
+    diff.changes
+}
+
+#[derive(Default)]
+struct Diff {
+    changes: Vec<Change>,
+}
+
+impl Diff {
+    fn breaking(&mut self, description: String) {
+        self.changes.push(Change {
+            breaking: true,
+            description,
+        });
+    }
+
+    fn compatible(&mut self, description: String) {
+        self.changes.push(Change {
+            breaking: false,
+            description,
+        });
+    }
+
+    /// Compares the argument or body types of a request or event, which are
+    /// not in `generated_types` when they are `()` or another type.
+    fn compare_bodies(&mut self, what: &str, old: &str, new: &str) {
+        if old != new {
+            let old = old.replace("crate::", "");
+            let new = new.replace("crate::", "");
+            self.breaking(format!("{what} changed type from `{old}` to `{new}`"));
+        }
+    }
+
+    fn compare_types(&mut self, name: &str, old: &Type, new: &Type) {
+        match (old, new) {
+            (Type::Object(old), Type::Object(new)) => self.compare_objects(name, old, new),
+            (Type::Enum(old), Type::Enum(new)) => self.compare_enums(name, old, new),
+            _ => self.breaking(format!("`{name}` changed between a struct and an enum")),
+        }
+    }
+
+    fn compare_objects(&mut self, name: &str, old: &Object, new: &Object) {
+        for old_field in &old.fields {
+            let path = format!("{name}.{}", to_snake_case(&old_field.name));
+            let Some(new_field) = new.fields.iter().find(|f| f.name == old_field.name) else {
+                self.breaking(format!("removed field `{path}`"));
+                continue;
+            };
+            let inline_name = format!("{name}{}", to_pascal_case(&old_field.name));
+            let old_ty = type_name(&old_field.ty, &inline_name);
+            let new_ty = type_name(&new_field.ty, &inline_name);
+            if old_ty != new_ty {
+                self.breaking(format!(
+                    "field `{path}` changed type from `{old_ty}` to `{new_ty}`"
+                ));
+            }
+            match (old_field.required, new_field.required) {
+                (true, false) => self.breaking(format!("field `{path}` became optional")),
+                (false, true) => self.breaking(format!("field `{path}` became required")),
+                _ => {}
+            }
+            if let (Some(old), Some(new)) = (inline(&old_field.ty), inline(&new_field.ty)) {
+                self.compare_types(&inline_name, old, new);
+            }
+        }
+        for new_field in &new.fields {
+            if !old.fields.iter().any(|f| f.name == new_field.name) {
+                // Struct literals have to name every field.
+                let kind = if new_field.required {
+                    "required"
+                } else {
+                    "optional"
+                };
+                let field = to_snake_case(&new_field.name);
+                self.breaking(format!("added {kind} field `{name}.{field}`"));
+            }
+        }
+    }
+
+    fn compare_enums(&mut self, name: &str, old: &Enum, new: &Enum) {
+        match (old.exhaustive, new.exhaustive) {
+            (true, false) => self.breaking(format!("enum `{name}` became non-exhaustive")),
+            (false, true) => self.breaking(format!("enum `{name}` became exhaustive")),
+            _ => {}
+        }
+        for variant in &old.variants {
+            if !new.variants.contains(variant) {
+                let variant = to_pascal_case(variant);
+                self.breaking(format!("removed variant `{name}::{variant}`"));
+            }
+        }
+        for variant in &new.variants {
+            if !old.variants.contains(variant) {
+                let description = format!("added variant `{name}::{}`", to_pascal_case(variant));
+                // Non-exhaustive enums are `#[non_exhaustive]`.
+                if new.exhaustive {
+                    self.breaking(description);
+                } else {
+                    self.compatible(description);
+                }
+            }
+        }
+    }
+}
+
+/// Types written to `types.rs`, by name. Events and responses are represented
+/// by their bodies, and requests by their argument types. Bodies that are not
+/// objects don't get a type of their own; they are compared by
+/// `Diff::compare_bodies` instead.
+fn generated_types(types: &[ProtocolType]) -> IndexMap<&str, &Type> {
+    let mut generated = IndexMap::new();
+    for ty in types {
+        if ty.name.ends_with("Request") {
+            continue;
+        }
+        let generated_ty = if ty.name.ends_with("Response") || ty.name.ends_with("Event") {
+            match &ty.ty.as_object().find_field("body").unwrap().ty {
+                body @ Type::Object(_) => body,
+                _ => continue,
+            }
+        } else {
+            &ty.ty
+        };
+        generated.insert(ty.name.as_str(), generated_ty);
+    }
+    generated
+}
+
+/// Rust type of a field, where inline enums and structs are named by `inline_name`.
+fn type_name(ty: &Type, inline_name: &str) -> String {
+    ty.stringify(inline_name.to_owned(), &mut Vec::new())
+}
+
+/// The inline enum or struct of a field, if it has one.
+fn inline(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Enum(_) | Type::Object(_) => Some(ty),
+        Type::Vec(ty) | Type::Option(ty) => inline(ty),
+        Type::Any | Type::Basic(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_protocol_types;
+    use crate::overrides::Overrides;
+    use serde_json::{json, Value};
+
+    fn types(definitions: Value) -> Vec<ProtocolType> {
+        generate_protocol_types(
+            &json!({ "definitions": definitions }),
+            &Overrides::default(),
+        )
+    }
+
+    fn changes(old: Value, new: Value) -> Vec<String> {
+        diff(&types(old), &types(new))
+            .iter()
+            .map(|c| c.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn reports_field_changes() {
+        let old = json!({
+            "Thing": {
+                "type": "object",
+                "properties": {
+                    "a": { "type": "string" },
+                    "b": { "type": "string" },
+                    "c": { "type": "integer" },
+                    "kind": { "type": "string", "enum": ["x", "y"] },
+                },
+                "required": ["a"],
+            },
+            "Gone": { "type": "object", "properties": {} },
+        });
+        let new = json!({
+            "Thing": {
+                "type": "object",
+                "properties": {
+                    "a": { "type": "string" },
+                    "c": { "type": "string" },
+                    "d": { "type": "boolean" },
+                    "kind": { "type": "string", "enum": ["x", "y", "z"] },
+                },
+                "required": ["a", "c"],
+            },
+            "Added": { "type": "object", "properties": {} },
+        });
+        let mut changes = changes(old, new);
+        changes.sort();
+        assert_eq!(
+            changes,
+            [
+                "breaking: added optional field `Thing.d`",
+                "breaking: added variant `ThingKind::Z`",
+                "breaking: field `Thing.c` became required",
+                "breaking: field `Thing.c` changed type from `u64` to `String`",
+                "breaking: removed field `Thing.b`",
+                "breaking: removed type `Gone`",
+                "compatible: added type `Added`",
+            ]
+        );
+    }
+
+    #[test]
+    fn open_enums_can_grow() {
+        let reason = |values: Value| {
+            json!({
+                "Reason": { "type": "string", "_enum": values },
+            })
+        };
+        let changes = changes(reason(json!(["a", "b"])), reason(json!(["a", "c"])));
+        assert_eq!(
+            changes,
+            [
+                "breaking: removed variant `Reason::B`",
+                "compatible: added variant `Reason::C`",
+            ]
+        );
+    }
+
+    /// Adds the base message types that requests and events extend.
+    fn messages(definitions: Value) -> Value {
+        let any = json!(["array", "boolean", "integer", "null", "number", "object", "string"]);
+        let base = |kind: &str, name: &str| {
+            json!({
+                "allOf": [
+                    { "$ref": "#/definitions/ProtocolMessage" },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "type": { "type": "string", "enum": [kind] },
+                            name: { "type": "string" },
+                            "arguments": { "type": any },
+                            "body": { "type": any },
+                        },
+                        "required": ["type", name],
+                    },
+                ],
+            })
+        };
+        let mut all = json!({
+            "ProtocolMessage": {
+                "type": "object",
+                "properties": { "seq": { "type": "integer" } },
+                "required": ["seq"],
+            },
+            "Event": base("event", "event"),
+            "Request": base("request", "command"),
+            "Response": base("response", "command"),
+        });
+        all.as_object_mut()
+            .unwrap()
+            .extend(definitions.as_object().unwrap().clone());
+        all
+    }
+
+    /// A message extending `base`, with `value` as its event or command.
+    fn message(base: &str, name: &str, value: &str, body: Option<Value>) -> Value {
+        let key = if base == "Event" { "event" } else { "command" };
+        let mut properties = json!({ key: { "type": "string", "enum": [value] } });
+        if let Some(body) = body {
+            properties["body"] = body;
+        }
+        json!({
+            "allOf": [
+                { "$ref": format!("#/definitions/{base}") },
+                { "type": "object", "description": name, "properties": properties },
+            ],
+        })
+    }
+
+    #[test]
+    fn reports_request_and_event_changes() {
+        let count = json!({
+            "type": "object",
+            "properties": { "count": { "type": "integer" } },
+        });
+        let old = messages(json!({
+            "PingEvent": message("Event", "PingEvent", "ping", None),
+            "GoneEvent": message("Event", "GoneEvent", "gone", None),
+            "PingRequest": message("Request", "PingRequest", "ping", None),
+            "PingResponse": message("Response", "PingResponse", "ping", None),
+        }));
+        let new = messages(json!({
+            "PingEvent": message("Event", "PingEvent", "ping", Some(count.clone())),
+            "AddedEvent": message("Event", "AddedEvent", "added", None),
+            "PingRequest": message("Request", "PingRequest", "ping", None),
+            "PingResponse": message("Response", "PingResponse", "ping", Some(count)),
+        }));
+        let mut changes = changes(old, new);
+        changes.sort();
+        assert_eq!(
+            changes,
+            [
+                "breaking: added event `added`",
+                "breaking: body of event `ping` changed type from `()` to `Option<PingEvent>`",
+                "breaking: removed event `gone`",
+                "breaking: response of request `ping` changed type from `()` to `PingResponse`",
+                "compatible: added type `PingEvent`",
+                "compatible: added type `PingResponse`",
+            ]
+        );
+    }
+}
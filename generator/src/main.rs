@@ -1,44 +1,137 @@
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
 use indexmap::IndexMap;
-use serde_json::{json, Map, Value};
+#[cfg(test)]
+use serde_json::json;
+use serde_json::{Map, Value};
 
-fn main() {
-    let schema = load_schema();
-    let protocol_types = generate_protocol_types(&schema);
-    let types = write_types(&protocol_types);
-    let requests = write_requests(&protocol_types);
-    let events = write_events(&protocol_types);
-    write_file("types.rs", &types);
-    write_file("requests.rs", &requests);
-    write_file("events.rs", &events);
-    println!("{types}");
+mod diff;
+mod overrides;
+
+use crate::overrides::Overrides;
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.as_slice() {
+        [] => {
+            for (file, contents) in generate_files(&load_schemas(), &load_overrides()) {
+                write_file(&file, &contents);
+            }
+            ExitCode::SUCCESS
+        }
+        [command, old, new] if command == "diff" => {
+            let overrides = load_overrides();
+            let old = generate_protocol_types(&load_schema(Path::new(old)), &overrides);
+            let new = generate_protocol_types(&load_schema(Path::new(new)), &overrides);
+            let changes = diff::diff(&old, &new);
+            for change in &changes {
+                println!("{change}");
+            }
+            // Breaking changes fail the command, so that it can be used in CI.
+            if changes.iter().any(|c| c.breaking) {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        _ => {
+            eprintln!("usage: generator [diff OLD_SCHEMA NEW_SCHEMA]");
+            ExitCode::from(2)
+        }
+    }
 }
 
 #[test]
 fn generated_files_are_up_to_date() {
-    fn check_file(file: &str, contents: &str) {
-        let want = with_disclaimer(contents);
-        let got = std::fs::read_to_string(dst_path(file)).unwrap();
+    for (file, contents) in generate_files(&load_schemas(), &load_overrides()) {
+        let want = with_disclaimer(&contents);
+        let got = std::fs::read_to_string(dst_path(&file)).unwrap();
         assert!(want == got, "file {} is not up to date", file);
     }
-    let schema = load_schema();
-    let protocol_types = generate_protocol_types(&schema);
-    let types = write_types(&protocol_types);
-    let requests = write_requests(&protocol_types);
-    let events = write_events(&protocol_types);
-    check_file("types.rs", &types);
-    check_file("requests.rs", &requests);
-    check_file("events.rs", &events);
+}
+
+/// Generates the contents of every generated file, keyed by path relative to
+/// `dap-types/src`.
+///
+/// The latest schema version is generated at the crate root. Older versions
+/// get their types, requests and events generated in `versions/v<major>_<minor>`.
+fn generate_files(schemas: &[Schema], overrides: &Overrides) -> Vec<(String, String)> {
+    let versions = generate_versions(schemas, overrides);
+    overrides.check_used();
+    let ((_, latest), older) = versions.split_last().expect("no schemas");
+    let mut files = vec![
+        ("types.rs".to_owned(), write_types(latest)),
+        (
+            "requests.rs".to_owned(),
+            write_requests(latest, Target::Latest),
+        ),
+        ("events.rs".to_owned(), write_events(latest, Target::Latest)),
+        ("adapter.rs".to_owned(), write_adapter(latest)),
+        ("versions.rs".to_owned(), write_versions(&versions)),
+    ];
+    for (version, types) in older {
+        files.extend(write_version(version, types));
+    }
+    files
+}
+
+/// Module that requests and events are generated into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    /// The crate root, for the latest version.
+    Latest,
+    /// A `versions::v<major>_<minor>` module, for an older version.
+    Older,
+}
+
+impl Target {
+    /// Path of the module with the types, as seen from requests and events.
+    fn types(self) -> &'static str {
+        match self {
+            Target::Latest => "crate",
+            Target::Older => "super",
+        }
+    }
+}
+
+/// Generates the files of an older protocol version.
+fn write_version(version: &str, types: &[ProtocolType]) -> Vec<(String, String)> {
+    let module = version_module(version);
+    let mut writer = Writer::default();
+    writer.line("pub mod events;");
+    writer.line("pub mod requests;");
+    writer.line("mod types;");
+    writer.finished_object();
+    writer.line("pub use types::*;");
+    vec![
+        (format!("versions/{module}.rs"), writer.output),
+        (format!("versions/{module}/types.rs"), write_types(types)),
+        (
+            format!("versions/{module}/requests.rs"),
+            write_requests(types, Target::Older),
+        ),
+        (
+            format!("versions/{module}/events.rs"),
+            write_events(types, Target::Older),
+        ),
+    ]
 }
 
 fn write_file(file: &str, contents: &str) {
     let contents = with_disclaimer(contents);
-    std::fs::write(dst_path(file), contents).unwrap();
+    let path = dst_path(file);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
 }
 
 fn with_disclaimer(contents: &str) -> String {
-    let disclaimer = "// This file is autogenerated. Do not edit by hand.\n// To regenerate from schema, run `cargo run -p generator`.\n\n";
+    // Docs are copied from the schema as they are, including list items
+    // followed by unindented lines.
+    let disclaimer = "// This file is autogenerated. Do not edit by hand.\n// To regenerate from schema, run `cargo run -p generator`.\n\n#![allow(clippy::doc_lazy_continuation)]\n\n";
     disclaimer.to_owned() + contents
 }
 
@@ -53,14 +146,372 @@ fn dst_path(file: &str) -> PathBuf {
     path
 }
 
-fn load_schema() -> Value {
+/// A version of the protocol schema.
+struct Schema {
+    /// Protocol version, like `1.65`.
+    version: String,
+    value: Value,
+}
+
+/// Loads the schemas in the `schemas` directory, which are named after their
+/// protocol version (e.g. `schemas/1.65.json`), from the oldest to the latest.
+fn load_schemas() -> Vec<Schema> {
     let workspace_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
         .parent()
         .unwrap();
-    let mut schema_path = workspace_dir.to_owned();
-    schema_path.push("schema.json");
-    let contents = std::fs::read_to_string(&schema_path).unwrap();
-    serde_json::from_str(&contents).unwrap()
+    let mut schemas = Vec::new();
+    for entry in std::fs::read_dir(workspace_dir.join("schemas")).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|e| e != "json") {
+            continue;
+        }
+        let version = path.file_stem().unwrap().to_str().unwrap().to_owned();
+        assert!(
+            version.split('.').all(|part| part.parse::<u32>().is_ok()),
+            "schema {} is not named after a protocol version",
+            path.display(),
+        );
+        schemas.push(Schema {
+            version,
+            value: load_schema(&path),
+        });
+    }
+    schemas.sort_by_key(|s| version_key(&s.version));
+    schemas
+}
+
+fn load_overrides() -> Overrides {
+    Overrides::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("overrides.json"))
+}
+
+fn load_schema(path: &Path) -> Value {
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+    serde_json::from_str(&contents)
+        .unwrap_or_else(|e| panic!("invalid schema {}: {e}", path.display()))
+}
+
+fn version_key(version: &str) -> Vec<u32> {
+    version
+        .split('.')
+        .map(|part| part.parse().unwrap())
+        .collect()
+}
+
+/// Name of the module for a protocol version, like `v1_65`.
+fn version_module(version: &str) -> String {
+    format!("v{}", version.replace('.', "_"))
+}
+
+/// Translates every schema version, noting in the docs of each version the
+/// version that added its types and fields.
+fn generate_versions(
+    schemas: &[Schema],
+    overrides: &Overrides,
+) -> Vec<(String, Vec<ProtocolType>)> {
+    let mut defined = Vec::new();
+    let mut versions = Vec::new();
+    for schema in schemas {
+        let mut types = generate_protocol_types(&schema.value, overrides);
+        let paths = defined_paths(&types);
+        annotate_since(&mut types, &schema.version, &defined);
+        defined.push((schema.version.clone(), paths));
+        versions.push((schema.version.clone(), types));
+    }
+    versions
+}
+
+/// Paths of the types and fields defined by a schema, like `Capabilities` or
+/// `StoppedEvent.body.reason`.
+fn defined_paths(types: &[ProtocolType]) -> HashSet<String> {
+    fn collect(path: &str, ty: &Type, paths: &mut HashSet<String>) {
+        match ty {
+            Type::Object(o) => {
+                for field in &o.fields {
+                    let path = format!("{path}.{}", field.name);
+                    collect(&path, &field.ty, paths);
+                    paths.insert(path);
+                }
+            }
+            Type::Vec(ty) | Type::Option(ty) => collect(path, ty, paths),
+            Type::Any | Type::Basic(_) | Type::Enum(_) => {}
+        }
+    }
+    let mut paths = HashSet::new();
+    for ty in types {
+        collect(&ty.name, &ty.ty, &mut paths);
+        paths.insert(ty.name.clone());
+    }
+    paths
+}
+
+/// Adds the version that introduced them to the docs of the types and fields
+/// of `version` that were not defined by the oldest of the earlier versions.
+/// Fields that were added together with their type are not annotated.
+///
+/// Nothing is annotated when there are no earlier versions, as then the version
+/// that introduced something is not known.
+fn annotate_since(
+    types: &mut [ProtocolType],
+    version: &str,
+    earlier: &[(String, HashSet<String>)],
+) {
+    struct Versions<'a> {
+        version: &'a str,
+        earlier: &'a [(String, HashSet<String>)],
+    }
+
+    impl<'a> Versions<'a> {
+        /// Version that added `path`, or `None` if the oldest version has it.
+        fn since(&self, path: &str) -> Option<&'a str> {
+            match self
+                .earlier
+                .iter()
+                .position(|(_, paths)| paths.contains(path))
+            {
+                Some(0) => None,
+                Some(i) => Some(&self.earlier[i].0),
+                None => Some(self.version),
+            }
+        }
+    }
+
+    fn annotate(doc: &mut Option<String>, since: &str) {
+        let note = format!("Since protocol version {since}.");
+        *doc = Some(match doc.take() {
+            Some(doc) => format!("{doc}\n\n{note}"),
+            None => note,
+        });
+    }
+
+    fn annotate_fields(path: &str, since: Option<&str>, ty: &mut Type, versions: &Versions) {
+        match ty {
+            Type::Object(o) => {
+                for field in &mut o.fields {
+                    let path = format!("{path}.{}", field.name);
+                    let field_since = versions.since(&path);
+                    if let Some(field_since) = field_since.filter(|&v| Some(v) != since) {
+                        annotate(&mut field.doc, field_since);
+                    }
+                    annotate_fields(&path, field_since, &mut field.ty, versions);
+                }
+            }
+            Type::Vec(ty) | Type::Option(ty) => annotate_fields(path, since, ty, versions),
+            Type::Any | Type::Basic(_) | Type::Enum(_) => {}
+        }
+    }
+
+    if earlier.is_empty() {
+        return;
+    }
+    let versions = Versions { version, earlier };
+    for ty in types {
+        let since = versions.since(&ty.name);
+        if let Some(since) = since {
+            match &mut ty.ty {
+                Type::Object(o) => annotate(&mut o.doc, since),
+                Type::Enum(e) => annotate(&mut e.doc, since),
+                Type::Any | Type::Basic(_) | Type::Vec(_) | Type::Option(_) => {}
+            }
+        }
+        annotate_fields(&ty.name, since, &mut ty.ty, &versions);
+    }
+}
+
+fn write_versions(versions: &[(String, Vec<ProtocolType>)]) -> String {
+    let mut writer = Writer::default();
+    let (latest, _) = versions.last().unwrap();
+    writer.doc("Protocol version of the types at the crate root.");
+    writer.line(format!("pub const LATEST: &str = {latest:?};"));
+    writer.finished_object();
+    writer.doc("Protocol versions that types are generated for, from the oldest to the latest.");
+    let items = versions
+        .iter()
+        .map(|(v, _)| format!("{v:?}"))
+        .collect::<Vec<_>>();
+    if items.join(", ").len() <= 60 {
+        writer.line(format!(
+            "pub const VERSIONS: &[&str] = &[{}];",
+            items.join(", ")
+        ));
+    } else {
+        // rustfmt fills lines with short array items.
+        writer.line("pub const VERSIONS: &[&str] = &[");
+        let mut line = String::new();
+        for item in items {
+            if !line.is_empty() && 4 + line.len() + 1 + item.len() + 1 > 100 {
+                writer.indented(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&item);
+            line.push(',');
+        }
+        writer.indented(line);
+        writer.line("];");
+    }
+    for (version, _) in &versions[..versions.len() - 1] {
+        let module = version_module(version);
+        writer.finished_object();
+        writer.doc(format!(
+            "Types, requests and events of protocol version {version}."
+        ));
+        writer.line(format!("pub mod {module};"));
+    }
+    writer.output
+}
+
+#[test]
+fn added_types_and_fields_are_documented() {
+    let thing = |properties: Value| {
+        json!({
+            "type": "object",
+            "description": "A thing.",
+            "properties": properties,
+        })
+    };
+    let schema = |version: &str, definitions: Value| Schema {
+        version: version.to_owned(),
+        value: json!({ "definitions": definitions }),
+    };
+    let a = json!({ "type": "string", "description": "Old field." });
+    let b = json!({ "type": "string", "description": "New field." });
+    let schemas = [
+        schema("1.9", json!({ "Thing": thing(json!({ "a": a })) })),
+        schema(
+            "1.10",
+            json!({
+                "Thing": thing(json!({ "a": a, "b": b })),
+                "Other": thing(json!({ "c": b })),
+            }),
+        ),
+    ];
+    let versions = generate_versions(&schemas, &Overrides::default());
+    assert_eq!(versions[0].0, "1.9");
+    assert!(!write_types(&versions[0].1).contains("Since"));
+
+    let types = write_types(&versions[1].1);
+    assert!(types.contains("/// Old field.\n    #[serde"));
+    assert!(types.contains("/// New field.\n    ///\n    /// Since protocol version 1.10.\n"));
+    // Fields of a new type are not annotated on their own.
+    assert!(types.contains("/// A thing.\n///\n/// Since protocol version 1.10.\n#[derive"));
+    assert_eq!(types.matches("Since").count(), 2);
+
+    let versions = write_versions(&versions);
+    assert!(versions.contains("pub const VERSIONS: &[&str] = &[\"1.9\", \"1.10\"];"));
+    assert!(versions.contains("\npub mod v1_9;"));
+    assert!(!versions.contains("cfg"));
+}
+
+#[test]
+fn older_versions_get_requests_and_events() {
+    let schema = load_schemas().pop().unwrap();
+    let types = generate_protocol_types(&schema.value, &load_overrides());
+    let files = write_version("1.9", &types);
+    let file = |name: &str| &files.iter().find(|(file, _)| file == name).unwrap().1;
+    assert_eq!(files.len(), 4);
+    assert!(file("versions/v1_9.rs").contains("pub mod requests;\n"));
+    let requests = file("versions/v1_9/requests.rs");
+    assert!(requests.contains("type Arguments = super::PauseArguments;"));
+    assert!(requests.contains("pub enum Requests {"));
+    // Helpers of the crate are only generated for the latest version.
+    assert!(!requests.contains("pub(crate)"));
+    assert!(!requests.contains("CAPABILITY_GATED_FIELDS"));
+    let events = file("versions/v1_9/events.rs");
+    assert!(events.contains("type Body = super::StoppedEvent;"));
+    assert!(!events.contains("pub(crate)"));
+}
+
+/// Checks that older versions compile, by generating the latest schema as an
+/// older version too into a copy of `dap-types` and running `cargo check` on it.
+#[test]
+fn older_versions_compile() {
+    fn copy_dir(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let to = to.join(entry.file_name());
+            if entry.file_type().unwrap().is_dir() {
+                copy_dir(&entry.path(), &to);
+            } else {
+                std::fs::copy(entry.path(), to).unwrap();
+            }
+        }
+    }
+
+    let workspace_dir = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let crate_dir = std::env::temp_dir().join(format!("dap-types-{}", std::process::id()));
+    copy_dir(&workspace_dir.join("dap-types/src"), &crate_dir.join("src"));
+    // The copy is a workspace of its own, locked to the same dependencies.
+    let manifest = std::fs::read_to_string(workspace_dir.join("dap-types/Cargo.toml")).unwrap();
+    std::fs::write(crate_dir.join("Cargo.toml"), manifest + "\n[workspace]\n").unwrap();
+    if workspace_dir.join("Cargo.lock").exists() {
+        std::fs::copy(
+            workspace_dir.join("Cargo.lock"),
+            crate_dir.join("Cargo.lock"),
+        )
+        .unwrap();
+    }
+
+    let latest = load_schemas().pop().unwrap();
+    let older = Schema {
+        version: "1.9".to_owned(),
+        value: latest.value.clone(),
+    };
+    let files = generate_files(&[older, latest], &load_overrides());
+    assert!(files
+        .iter()
+        .any(|(file, _)| file == "versions/v1_9/events.rs"));
+    for (file, contents) in files {
+        let path = crate_dir.join("src").join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, with_disclaimer(&contents)).unwrap();
+    }
+
+    for features in [&[][..], &["--all-features"]] {
+        let status = std::process::Command::new(env!("CARGO"))
+            .args(["check", "--quiet", "--lib"])
+            .args(features)
+            .current_dir(&crate_dir)
+            .env(
+                "CARGO_TARGET_DIR",
+                workspace_dir.join("target/older-versions"),
+            )
+            .status()
+            .unwrap();
+        assert!(
+            status.success(),
+            "older versions do not compile with {features:?}"
+        );
+    }
+    std::fs::remove_dir_all(crate_dir).unwrap();
+}
+
+#[test]
+fn types_containing_floats_do_not_derive_eq() {
+    let object = |properties: Value| json!({ "type": "object", "properties": properties });
+    let types = generate_protocol_types(
+        &json!({
+            "definitions": {
+                "Progress": object(json!({ "percentage": { "type": "number" } })),
+                "Outer": object(json!({
+                    "items": { "type": "array", "items": { "$ref": "#/definitions/Progress" } },
+                })),
+                "Plain": object(json!({ "count": { "type": "integer" } })),
+            },
+        }),
+        &Overrides::default(),
+    );
+    let types = write_types(&types);
+    let derives = |name: &str| {
+        let (before, _) = types.split_once(&format!("pub struct {name} ")).unwrap();
+        before.lines().last().unwrap().to_owned()
+    };
+    assert!(types.contains("pub percentage: Option<f64>,"));
+    assert!(!derives("Progress").contains(", Eq"));
+    assert!(!derives("Outer").contains(", Eq"));
+    assert!(derives("Plain").contains("Eq, Hash"));
 }
 
 const BLACKLISTED_TYPES: &[&str] = &[
@@ -73,12 +524,23 @@ const BLACKLISTED_TYPES: &[&str] = &[
     "AttachRequestArguments",
 ];
 
-fn write_requests(types: &[ProtocolType]) -> String {
-    let mut writer = Writer::default();
-    writer.line("use serde::{de::DeserializeOwned, Serialize};");
-    writer.line("use std::fmt::Debug;");
-    writer.finished_object();
-    writer.code(REQUEST_TRAIT);
+/// A request defined by the protocol, with its argument and response body types.
+struct RequestDef {
+    /// Name of the marker type, e.g. `SetBreakpoints`.
+    name: String,
+    command: String,
+    arguments: String,
+    response: String,
+    doc: String,
+    /// Adapter capability that has to be true for clients to send this request.
+    capability: Option<String>,
+}
+
+/// Requests that are sent by the debug adapter to the client.
+const REVERSE_REQUESTS: &[&str] = &["runInTerminal", "startDebugging"];
+
+fn collect_requests(types: &[ProtocolType], target: Target) -> Vec<RequestDef> {
+    let mut requests = Vec::new();
     for ty in types {
         let Type::Object(o) = &ty.ty else {
             continue;
@@ -94,9 +556,8 @@ fn write_requests(types: &[ProtocolType]) -> String {
         }
         let command = o.find_field("command").unwrap().ty.as_enum().single_value();
         let arguments = match &o.find_field("arguments").unwrap().ty {
-            Type::Any if command == "threads" => "crate::ThreadsArgument".to_owned(),
             Type::Any => "()".to_owned(),
-            Type::Basic(args) => format!("crate::{args}"),
+            Type::Basic(args) => format!("{}::{args}", target.types()),
             _ => panic!("bad arguments type for {}", ty.name),
         };
         let request = ty.name.strip_suffix("Request").unwrap();
@@ -105,29 +566,448 @@ fn write_requests(types: &[ProtocolType]) -> String {
         let ro = response_ty.ty.as_object();
         let response_body = match &ro.find_field("body").unwrap().ty {
             Type::Any => "()".to_owned(),
-            Type::Basic(body) => format!("crate::{body}"),
-            Type::Object(_) => format!("crate::{response}"),
+            Type::Basic(body) => format!("{}::{body}", target.types()),
+            Type::Object(_) => format!("{}::{response}", target.types()),
             _ => panic!("bad response body for {}", ty.name),
         };
-        writer.doc(o.doc.as_ref().unwrap());
-        writer.line(format!("pub enum {request} {{}}"));
+        let doc = o.doc.clone().unwrap();
+        requests.push(RequestDef {
+            name: request.to_owned(),
+            command: command.to_owned(),
+            arguments,
+            response: response_body,
+            capability: required_capability(&doc),
+            doc,
+        });
+    }
+    requests
+}
+
+/// Finds the capability gating a request in its description, which says e.g.
+/// "Clients should only call this request if the corresponding capability
+/// `supportsCancelRequest` is true".
+fn required_capability(doc: &str) -> Option<String> {
+    let (_, rest) = doc.split_once("should only call this request if the ")?;
+    let rest = rest.strip_prefix("corresponding ").unwrap_or(rest);
+    let (capability, rest) = rest.strip_prefix("capability `")?.split_once('`')?;
+    // `exceptionBreakpointFilters` is a list, not a flag that can be true.
+    rest.starts_with(" is true").then(|| capability.to_owned())
+}
+
+fn write_requests(types: &[ProtocolType], target: Target) -> String {
+    let mut writer = Writer::default();
+    writer.line("use serde::{de::DeserializeOwned, Serialize, Serializer};");
+    writer.line("use serde_json::Value;");
+    writer.line("use std::fmt::Debug;");
+    writer.finished_object();
+    writer.line("use crate::messages::{decode_optional, OtherRequest, RequestParts};");
+    writer.finished_object();
+    writer.code(REQUEST_TRAIT);
+    let requests = collect_requests(types, target);
+    for request in &requests {
+        let RequestDef {
+            name,
+            command,
+            arguments,
+            response,
+            doc,
+            ..
+        } = request;
+        writer.doc(doc);
+        writer.line(format!("pub enum {name} {{}}"));
         writer.finished_object();
-        writer.line(format!("impl Request for {request} {{"));
+        writer.line(format!("impl Request for {name} {{"));
         writer.indented(format!("const COMMAND: &'static str = {command:?};"));
+        if let Some(capability) = &request.capability {
+            writer.indented(format!(
+                "const REQUIRED_CAPABILITY: Option<&'static str> = Some({capability:?});"
+            ));
+        }
         writer.indented(format!("type Arguments = {arguments};"));
-        writer.indented(format!("type Response = {response_body};"));
+        writer.indented(format!("type Response = {response};"));
         writer.line("}");
         writer.finished_object();
     }
+    write_capability_gated_requests(&requests, &mut writer);
+    // Gated fields and response checks are only used with the latest version.
+    if target == Target::Latest {
+        write_capability_gated_fields(types, &requests, &mut writer);
+    }
+    write_requests_enum(&requests, &mut writer);
+    if target == Target::Latest {
+        write_check_response_body(&requests, &mut writer);
+    }
     writer.output
 }
 
-fn write_events(types: &[ProtocolType]) -> String {
+fn write_check_response_body(requests: &[RequestDef], writer: &mut Writer) {
+    writer.doc("Checks that the body of a successful response fits the response type of the command.\nReturns `None` for commands not defined by the protocol.");
+    writer.line("pub(crate) fn check_response_body(");
+    writer.line("    command: &str,");
+    writer.line("    body: Option<Value>,");
+    writer.line(") -> Option<Result<(), serde_json::Error>> {");
+    writer.indented("fn check<R: Request>(body: Option<Value>) -> Result<(), serde_json::Error> {");
+    writer.indented("    decode_optional::<R::Response>(body).map(drop)");
+    writer.indented("}");
+    writer.indented("Some(match command {");
+    for RequestDef { name, command, .. } in requests {
+        writer.match_arm(
+            2,
+            &format!("{command:?}"),
+            &format!("check::<{name}>(body)"),
+        );
+    }
+    writer.indented("    _ => return None,");
+    writer.indented("})");
+    writer.line("}");
+}
+
+/// Argument fields that adapters only honor if they advertise a capability, as
+/// `(capability, field path, commands)`. Field paths are field names joined by
+/// dots. Only some of these are mentioned in the docs of the fields, the others
+/// are described by the capabilities.
+const GATED_FIELDS: &[(&str, &str, &[&str])] = &[
+    (
+        "supportTerminateDebuggee",
+        "terminateDebuggee",
+        &["disconnect"],
+    ),
+    ("supportSuspendDebuggee", "suspendDebuggee", &["disconnect"]),
+    (
+        "supportsConditionalBreakpoints",
+        "breakpoints.condition",
+        BREAKPOINT_REQUESTS,
+    ),
+    (
+        "supportsHitConditionalBreakpoints",
+        "breakpoints.hitCondition",
+        BREAKPOINT_REQUESTS,
+    ),
+    (
+        "supportsLogPoints",
+        "breakpoints.logMessage",
+        &["setBreakpoints"],
+    ),
+    (
+        "supportsExceptionFilterOptions",
+        "filterOptions",
+        &["setExceptionBreakpoints"],
+    ),
+    (
+        "supportsExceptionOptions",
+        "exceptionOptions",
+        &["setExceptionBreakpoints"],
+    ),
+    (
+        "supportsDataBreakpointBytes",
+        "bytes",
+        &["dataBreakpointInfo"],
+    ),
+    (
+        "supportsDataBreakpointBytes",
+        "asAddress",
+        &["dataBreakpointInfo"],
+    ),
+    (
+        "supportsSingleThreadExecutionRequests",
+        "singleThread",
+        EXECUTION_REQUESTS,
+    ),
+    (
+        "supportsSteppingGranularity",
+        "granularity",
+        &["next", "stepIn", "stepOut", "stepBack"],
+    ),
+    (
+        "supportsValueFormattingOptions",
+        "format",
+        FORMATTING_REQUESTS,
+    ),
+    (
+        "supportsDelayedStackTraceLoading",
+        "startFrame",
+        &["stackTrace"],
+    ),
+    (
+        "supportsDelayedStackTraceLoading",
+        "levels",
+        &["stackTrace"],
+    ),
+];
+
+const BREAKPOINT_REQUESTS: &[&str] = &[
+    "setBreakpoints",
+    "setFunctionBreakpoints",
+    "setDataBreakpoints",
+    "setInstructionBreakpoints",
+];
+
+const EXECUTION_REQUESTS: &[&str] = &[
+    "continue",
+    "next",
+    "stepIn",
+    "stepOut",
+    "stepBack",
+    "reverseContinue",
+];
+
+const FORMATTING_REQUESTS: &[&str] = &[
+    "stackTrace",
+    "variables",
+    "setVariable",
+    "evaluate",
+    "setExpression",
+];
+
+/// Returns whether object type `ty` has a field at `path`, looking through
+/// arrays and optional fields along the way.
+fn has_field(types: &[ProtocolType], ty: &str, path: &str) -> bool {
+    let Some(Type::Object(o)) = types.iter().find(|t| t.name == ty).map(|t| &t.ty) else {
+        return false;
+    };
+    let (name, rest) = match path.split_once('.') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (path, None),
+    };
+    let Some(field) = o.fields.iter().find(|f| f.name == name) else {
+        return false;
+    };
+    let Some(rest) = rest else {
+        return true;
+    };
+    let mut field_ty = &field.ty;
+    while let Type::Vec(inner) | Type::Option(inner) = field_ty {
+        field_ty = inner;
+    }
+    matches!(field_ty, Type::Basic(name) if has_field(types, name, rest))
+}
+
+fn write_capability_gated_requests(requests: &[RequestDef], writer: &mut Writer) {
+    writer.doc("Requests that clients should only send if the adapter advertises a capability, as `(command, capability)` pairs.");
+    writer.line("pub const CAPABILITY_GATED_REQUESTS: &[(&str, &str)] = &[");
+    for request in requests {
+        if let Some(capability) = &request.capability {
+            writer.tuple(1, &[&request.command, capability]);
+        }
+    }
+    writer.line("];");
+    writer.finished_object();
+}
+
+fn write_capability_gated_fields(
+    types: &[ProtocolType],
+    requests: &[RequestDef],
+    writer: &mut Writer,
+) {
+    let capabilities = types.iter().find(|t| t.name == "Capabilities").unwrap();
+    let flags = capabilities
+        .ty
+        .as_object()
+        .fields
+        .iter()
+        .filter(|f| matches!(&f.ty, Type::Basic(t) if t == "bool"))
+        .map(|f| f.name.as_str())
+        .collect::<Vec<_>>();
+    for (capability, path, commands) in GATED_FIELDS {
+        assert!(
+            flags.contains(capability),
+            "{capability} is not a capability"
+        );
+        for command in *commands {
+            let request = requests.iter().find(|r| r.command == *command);
+            let arguments = request.and_then(|r| r.arguments.strip_prefix("crate::"));
+            assert!(
+                arguments.is_some_and(|a| has_field(types, a, path)),
+                "gated field {command} {path} is not in the schema"
+            );
+        }
+    }
+    writer.doc("Argument fields that adapters only honor if they advertise a capability, as `(command, field path, capability)` triples.\n\nField paths are field names joined by dots, which apply to every element of arrays along the way.");
+    writer.line("pub const CAPABILITY_GATED_FIELDS: &[(&str, &str, &str)] = &[");
+    for request in requests {
+        for (capability, path, commands) in GATED_FIELDS {
+            if commands.contains(&request.command.as_str()) {
+                writer.tuple(1, &[&request.command, path, capability]);
+            }
+        }
+    }
+    writer.line("];");
+    writer.finished_object();
+}
+
+fn write_requests_enum(requests: &[RequestDef], writer: &mut Writer) {
+    writer.doc("A request with typed arguments.\nRequests with commands not defined by the protocol are decoded as `Other`.");
+    writer.line("#[derive(Debug, Clone)]");
+    writer.line("pub enum Requests {");
+    for request in requests {
+        writer.indented(format!("{}({}),", request.name, request.arguments));
+    }
+    writer.indented("Other(OtherRequest),");
+    writer.line("}");
+    writer.finished_object();
+
+    writer.line("impl Requests {");
+    writer.indented_doc("Decodes request arguments according to the command.");
+    writer.indented("pub fn from_parts(");
+    writer.indented("    command: String,");
+    writer.indented("    arguments: Option<Value>,");
+    writer.indented(") -> Result<Self, serde_json::Error> {");
+    writer.indented("    Ok(match command.as_str() {");
+    for RequestDef { name, command, .. } in requests {
+        writer.match_arm(
+            3,
+            &format!("{command:?}"),
+            &format!("Requests::{name}(decode_optional(arguments)?)"),
+        );
+    }
+    writer.indented("        _ => Requests::Other(OtherRequest { command, arguments }),");
+    writer.indented("    })");
+    writer.indented("}");
+    writer.finished_object();
+    writer.indented_doc("Encodes request arguments, returning the command and the arguments.");
+    writer.indented(
+        "pub fn into_parts(self) -> Result<(String, Option<Value>), serde_json::Error> {",
+    );
+    writer.indented("    let command = self.command().to_owned();");
+    writer.indented("    let arguments = match self {");
+    for RequestDef { name, .. } in requests {
+        writer.match_arm(
+            3,
+            &format!("Requests::{name}(arguments)"),
+            "serde_json::to_value(arguments)?",
+        );
+    }
+    writer.indented("        Requests::Other(other) => return Ok((command, other.arguments)),");
+    writer.indented("    };");
+    writer.indented("    Ok((command, Some(arguments).filter(|a| !a.is_null())))");
+    writer.indented("}");
+    writer.finished_object();
+    writer.indented("pub fn command(&self) -> &str {");
+    writer.indented("    match self {");
+    for RequestDef { name, .. } in requests {
+        writer.match_arm(
+            3,
+            &format!("Requests::{name}(_)"),
+            &format!("{name}::COMMAND"),
+        );
+    }
+    writer.indented("        Requests::Other(other) => &other.command,");
+    writer.indented("    }");
+    writer.indented("}");
+    writer.line("}");
+    writer.finished_object();
+
+    writer.line("impl Serialize for Requests {");
+    writer
+        .indented("fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {");
+    writer.indented("    let command = self.command();");
+    writer.indented("    match self {");
+    for RequestDef { name, .. } in requests {
+        writer.match_arm(
+            3,
+            &format!("Requests::{name}(arguments)"),
+            "RequestParts { command, arguments }.serialize(serializer)",
+        );
+    }
+    writer.indented("        Requests::Other(other) => other.serialize(serializer),");
+    writer.indented("    }");
+    writer.indented("}");
+    writer.line("}");
+    writer.finished_object();
+}
+
+fn write_adapter(types: &[ProtocolType]) -> String {
     let mut writer = Writer::default();
-    writer.line("use serde::{de::DeserializeOwned, Serialize};");
-    writer.line("use std::fmt::Debug;");
+    writer.line("use std::future::Future;");
     writer.finished_object();
-    writer.code(EVENT_TRAIT);
+    writer.line("use crate::capabilities;");
+    writer.line("use crate::messages::{Request, Requests, Response};");
+    writer.line("use crate::requests::{self, Request as _};");
+    writer.finished_object();
+    writer.code(ADAPTER_PRELUDE);
+    let requests = collect_requests(types, Target::Latest)
+        .into_iter()
+        .filter(|r| !REVERSE_REQUESTS.contains(&r.command.as_str()))
+        .collect::<Vec<_>>();
+
+    writer.doc("Handler for requests sent by the client to a debug adapter.\n\nEvery method has a default implementation that responds with an \"unsupported request\" error, so an adapter only implements the requests it supports.\nRequests are dispatched to these methods by [`dispatch`].");
+    writer.line("pub trait DebugAdapter: Send {");
+    writer.indented_doc("Commands of the requests this adapter implements, e.g. `requests::StepBack::COMMAND`.\n\nWhen set, capabilities returned from [`DebugAdapter::initialize`] that gate these requests are filled in, and a warning is logged for capabilities that disagree with them (see [`crate::capabilities`]).\nA warning is also logged when a listed request reaches its default handler, which means that the handler is not implemented.");
+    writer.indented("const HANDLED_REQUESTS: &'static [&'static str] = &[];");
+    writer.finished_object();
+    for (
+        i,
+        RequestDef {
+            name,
+            arguments,
+            response,
+            doc,
+            ..
+        },
+    ) in requests.iter().enumerate()
+    {
+        if i != 0 {
+            writer.finished_object();
+        }
+        writer.indented_doc(doc);
+        writer.indented(format!("fn {}(", to_snake_case(name)));
+        writer.indented("    &mut self,");
+        writer.indented(format!("    arguments: {arguments},"));
+        writer.indented(format!(
+            ") -> impl Future<Output = HandlerResult<{response}>> + Send {{"
+        ));
+        writer.indented("    let _ = arguments;");
+        writer.indented(format!(
+            "    let error = not_implemented::<Self>(requests::{name}::COMMAND);"
+        ));
+        writer.indented("    async move { Err(error) }");
+        writer.indented("}");
+    }
+    writer.line("}");
+    writer.finished_object();
+
+    writer.doc("Handles a request by calling the matching [`DebugAdapter`] method.\n\nRequests that are not defined by the protocol, and requests with invalid arguments, get an error response.\nThe returned response has `seq` set to 0, it should be assigned when the response is sent.");
+    writer.line(
+        "pub async fn dispatch<A: DebugAdapter>(adapter: &mut A, request: &Request) -> Response {",
+    );
+    writer.indented("let typed = match request.typed() {");
+    writer.indented("    Ok(typed) => typed,");
+    writer.indented(
+        "    Err(e) => return AdapterError::invalid_arguments(e).into_response(request),",
+    );
+    writer.indented("};");
+    writer.indented("match typed {");
+    for RequestDef { name, .. } in &requests {
+        let method = to_snake_case(name);
+        writer.indented(format!("    Requests::{name}(arguments) => {{"));
+        writer.indented(format!(
+            "        let result = adapter.{method}(arguments).await;"
+        ));
+        if name == "Initialize" {
+            writer.indented("        let result = result.map(capabilities::complete::<A>);");
+        }
+        writer.indented(format!(
+            "        respond::<requests::{name}>(request, result)"
+        ));
+        writer.indented("    }");
+    }
+    writer.indented("    _ => AdapterError::unsupported(&request.command).into_response(request),");
+    writer.indented("}");
+    writer.line("}");
+    writer.output
+}
+
+/// An event defined by the protocol, with its body type.
+struct EventDef {
+    /// Name of the marker type, e.g. `Stopped`.
+    name: String,
+    event: String,
+    body: String,
+    doc: String,
+}
+
+fn collect_events(types: &[ProtocolType], target: Target) -> Vec<EventDef> {
+    let mut events = Vec::new();
     for ty in types {
         let Type::Object(o) = &ty.ty else {
             continue;
@@ -141,33 +1021,194 @@ fn write_events(types: &[ProtocolType]) -> String {
         if e.variants.len() != 1 || e.variants[0] != "event" || !e.exhaustive {
             continue;
         }
-        let name = o.find_field("event").unwrap().ty.as_enum().single_value();
+        let event = o.find_field("event").unwrap().ty.as_enum().single_value();
 
-        let body = match &o.find_field("body").unwrap().ty {
-            Type::Any => {
-                if name == "initialized" {
-                    format!("Option<crate::Capabilities>")
-                } else {
-                    "()".to_owned()
-                }
-            }
-            Type::Basic(args) => format!("crate::{args}"),
-            Type::Object(_) => format!("crate::{}", ty.name),
+        let body_field = o.find_field("body").unwrap();
+        let body = match &body_field.ty {
+            Type::Any => "()".to_owned(),
+            Type::Basic(args) => format!("{}::{args}", target.types()),
+            Type::Object(_) => format!("{}::{}", target.types(), ty.name),
             _ => panic!("bad body type for {}", ty.name),
         };
-        let event = ty.name.strip_suffix("Event").unwrap();
-        writer.doc(o.doc.as_ref().unwrap());
-        writer.line(format!("pub enum {event} {{}}"));
+        let body = match &body_field.ty {
+            Type::Basic(_) | Type::Object(_) if !body_field.required => format!("Option<{body}>"),
+            _ => body,
+        };
+        events.push(EventDef {
+            name: ty.name.strip_suffix("Event").unwrap().to_owned(),
+            event: event.to_owned(),
+            body,
+            doc: o.doc.clone().unwrap(),
+        });
+    }
+    events
+}
+
+fn write_events(types: &[ProtocolType], target: Target) -> String {
+    let mut writer = Writer::default();
+    writer.line("use serde::{de::DeserializeOwned, Deserialize, Serialize};");
+    if target == Target::Latest {
+        writer.line("use serde_json::Value;");
+    }
+    writer.line("use std::fmt::Debug;");
+    writer.finished_object();
+    writer.line("use crate::messages::OtherEvent;");
+    writer.finished_object();
+    writer.code(EVENT_TRAIT);
+    let events = collect_events(types, target);
+    for EventDef {
+        name,
+        event,
+        body,
+        doc,
+    } in &events
+    {
+        writer.doc(doc);
+        writer.line(format!("pub enum {name} {{}}"));
         writer.finished_object();
-        writer.line(format!("impl Event for {event} {{"));
-        writer.indented(format!("const EVENT: &'static str = {name:?};"));
+        writer.line(format!("impl Event for {name} {{"));
+        writer.indented(format!("const EVENT: &'static str = {event:?};"));
         writer.indented(format!("type Body = {body};"));
+        writer.finished_object();
+        // Events without a body are unit variants of `Events`.
+        if body == "()" {
+            writer.indented("fn into_events((): Self::Body) -> Events {");
+            writer.indented(format!("    Events::{name}"));
+        } else {
+            writer.indented("fn into_events(body: Self::Body) -> Events {");
+            writer.indented(format!("    Events::{name}(body)"));
+        }
+        writer.indented("}");
+        writer.finished_object();
+        writer.indented("fn from_events(events: &Events) -> Option<&Self::Body> {");
+        writer.indented("    match events {");
+        if body == "()" {
+            writer.indented(format!("        Events::{name} => Some(&()),"));
+        } else {
+            writer.indented(format!("        Events::{name}(body) => Some(body),"));
+        }
+        writer.indented("        _ => None,");
+        writer.indented("    }");
+        writer.indented("}");
         writer.line("}");
         writer.finished_object();
     }
+
+    write_events_enum(&events, &mut writer);
+    // Body checks are only used with the latest version.
+    if target == Target::Older {
+        return writer.output;
+    }
+
+    writer.doc("Checks that an event body fits the body type of the event.\nReturns `None` for events not defined by the protocol.");
+    writer.line(
+        "pub(crate) fn check_event_body(event: &str, body: Value) -> Option<Result<(), serde_json::Error>> {",
+    );
+    writer.indented("fn check<E: Event>(body: Value) -> Result<(), serde_json::Error> {");
+    writer.indented("    serde_json::from_value::<E::Body>(body).map(drop)");
+    writer.indented("}");
+    writer.indented("Some(match event {");
+    for EventDef { name, event, .. } in &events {
+        writer.match_arm(2, &format!("{event:?}"), &format!("check::<{name}>(body)"));
+    }
+    writer.indented("    _ => return None,");
+    writer.indented("})");
+    writer.line("}");
+    writer.finished_object();
+
+    writer.doc("Writes the body of an event to the map of its message.\nEvents without a body are encoded without one, instead of `null`.");
+    writer.line("pub(crate) fn serialize_event_body<M: serde::ser::SerializeMap>(");
+    writer.indented("event: &Events,");
+    writer.indented("map: &mut M,");
+    writer.line(") -> Result<(), M::Error> {");
+    writer.indented("match event {");
+    for EventDef { name, body, .. } in &events {
+        let pattern = if body == "()" {
+            continue;
+        } else if body.starts_with("Option<") {
+            format!("Events::{name}(Some(body))")
+        } else {
+            format!("Events::{name}(body)")
+        };
+        writer.match_arm(2, &pattern, "map.serialize_entry(\"body\", body)");
+    }
+    writer.match_arm(
+        2,
+        "Events::Other(other) if !other.body.is_null()",
+        "map.serialize_entry(\"body\", &other.body)",
+    );
+    writer.indented("    _ => Ok(()),");
+    writer.indented("}");
+    writer.line("}");
     writer.output
 }
 
+fn write_events_enum(events: &[EventDef], writer: &mut Writer) {
+    writer.doc("An event with a typed body.\nEvents not defined by the protocol, or with a body that does not match the event, are decoded as `Other`.");
+    writer.line("#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]");
+    writer.line("#[serde(tag = \"event\", content = \"body\")]");
+    writer.line("#[serde(rename_all = \"camelCase\")]");
+    writer.line("pub enum Events {");
+    for EventDef {
+        name, event, body, ..
+    } in events
+    {
+        // Variants are renamed with `rename_all`, which has to give back the event name.
+        assert_eq!(
+            &to_pascal_case(event),
+            name,
+            "event name does not match its variant"
+        );
+        if body == "()" {
+            writer.indented(format!("{name},"));
+        } else {
+            writer.indented(format!("{name}({body}),"));
+        }
+    }
+    writer.indented("#[serde(untagged)]");
+    writer.indented("Other(OtherEvent),");
+    writer.line("}");
+    writer.finished_object();
+
+    writer.line("impl Events {");
+    writer.indented_doc("Name of the event, as sent in the `event` field.");
+    writer.indented("pub fn name(&self) -> &str {");
+    writer.indented("    match self {");
+    for EventDef { name, body, .. } in events {
+        writer.match_arm(3, &events_pattern(name, body), &format!("{name}::EVENT"));
+    }
+    writer.indented("        Events::Other(other) => &other.event,");
+    writer.indented("    }");
+    writer.indented("}");
+    writer.line("}");
+    writer.finished_object();
+
+    writer.line("impl std::fmt::Display for Events {");
+    writer.indented("fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {");
+    writer.indented("    match self {");
+    for EventDef { name, body, .. } in events {
+        writer.match_arm(
+            3,
+            &events_pattern(name, body),
+            &format!("write!(f, {name:?})"),
+        );
+    }
+    writer.indented("        Events::Other(other) => write!(f, \"{}\", other.event.as_str()),");
+    writer.indented("    }");
+    writer.indented("}");
+    writer.line("}");
+    writer.finished_object();
+}
+
+/// Pattern matching any `Events` variant of event `name`.
+fn events_pattern(name: &str, body: &str) -> String {
+    if body == "()" {
+        format!("Events::{name}")
+    } else {
+        format!("Events::{name}(_)")
+    }
+}
+
 fn write_types(types: &[ProtocolType]) -> String {
     let mut writer = Writer::default();
     writer.line("use schemars::JsonSchema;");
@@ -178,19 +1219,7 @@ fn write_types(types: &[ProtocolType]) -> String {
             continue;
         }
         println!("writing type {}", ty.name);
-        if ty.name.ends_with("Response") {
-            let body = &ty.ty.as_object().find_field("body").unwrap().ty;
-            match body {
-                Type::Any => continue,
-                Type::Object(o) => {
-                    let mut o = o.clone();
-                    o.doc = o.doc.or(ty.ty.doc());
-                    o.write(&ty.name, &mut writer);
-                }
-                Type::Basic(_) => continue,
-                _ => panic!(),
-            }
-        } else if ty.name.ends_with("Event") {
+        if ty.name.ends_with("Response") || ty.name.ends_with("Event") {
             let body = &ty.ty.as_object().find_field("body").unwrap().ty;
             match body {
                 Type::Any => continue,
@@ -210,22 +1239,66 @@ fn write_types(types: &[ProtocolType]) -> String {
     writer.output
 }
 
-fn generate_protocol_types(schema: &Value) -> Vec<ProtocolType> {
+fn generate_protocol_types(schema: &Value, overrides: &Overrides) -> Vec<ProtocolType> {
     let defs = schema.get("definitions").unwrap().as_object().unwrap();
     let mut types = Vec::new();
     for (name, def) in defs {
         if BLACKLISTED_TYPES.contains(&name.as_str()) {
             continue;
         }
-        println!("generating {name}");
         types.push(ProtocolType {
             name: name.to_owned(),
             ty: translate_type(defs, def),
         });
     }
+    overrides.apply(&mut types);
+    mark_float_types(&mut types);
     types
 }
 
+/// Stops deriving `Eq` and `Hash` for objects that contain floats, directly or
+/// through other types.
+fn mark_float_types(types: &mut [ProtocolType]) {
+    let mut floats = HashSet::new();
+    loop {
+        let found = floats.len();
+        for ty in types.iter() {
+            if contains_float(&ty.ty, &floats) {
+                floats.insert(ty.name.clone());
+            }
+        }
+        if floats.len() == found {
+            break;
+        }
+    }
+    for ty in types {
+        mark_objects(&mut ty.ty, &floats);
+    }
+}
+
+fn contains_float(ty: &Type, floats: &HashSet<String>) -> bool {
+    match ty {
+        Type::Any | Type::Enum(_) => false,
+        Type::Basic(name) => name == "f64" || floats.contains(name),
+        Type::Object(o) => o.fields.iter().any(|f| contains_float(&f.ty, floats)),
+        Type::Vec(ty) | Type::Option(ty) => contains_float(ty, floats),
+    }
+}
+
+fn mark_objects(ty: &mut Type, floats: &HashSet<String>) {
+    let derives_eq = !contains_float(ty, floats);
+    match ty {
+        Type::Object(o) => {
+            o.derives_eq = derives_eq;
+            for field in &mut o.fields {
+                mark_objects(&mut field.ty, floats);
+            }
+        }
+        Type::Vec(ty) | Type::Option(ty) => mark_objects(ty, floats),
+        Type::Any | Type::Basic(_) | Type::Enum(_) => {}
+    }
+}
+
 fn translate_all_of(defs: &Map<String, Value>, def: &Value) -> Object {
     assert_eq!(def.as_object().unwrap().len(), 1);
     let members = def.get("allOf").unwrap().as_array().unwrap();
@@ -249,6 +1322,8 @@ fn translate_all_of(defs: &Map<String, Value>, def: &Value) -> Object {
     Object {
         doc,
         fields: fields.into_iter().map(|x| x.1).collect(),
+        derives_eq: true,
+        derives: Vec::new(),
     }
 }
 
@@ -282,30 +1357,21 @@ fn translate_object(defs: &Map<String, Value>, def: &Value) -> Object {
             .get("description")
             .map(|x| x.as_str().unwrap().to_owned()),
         fields: fields.into_iter().map(|x| x.1).collect(),
+        derives_eq: true,
+        derives: Vec::new(),
     }
 }
 
 fn generate_field(defs: &Map<String, Value>, name: &str, def: &Value, required: bool) -> Field {
-    let ty = if name == "hitBreakpointIds" || name == "threadIds" {
-        // Note: i64 is used instead of u64 here because delve returns -1 when a panic is hit.
-        // Everywhere else we assume (though the spec doesn't specify) that breakpoint ids are u64s.
-        // https://github.com/go-delve/delve/pull/4027
-        assert_eq!(def.get("type"), Some(&json!("array")));
-        assert_eq!(def.get("items"), Some(&json!({ "type": "integer" })));
-        Type::Vec(Box::new("i64".into()))
-    } else if name == "threadId" {
-        assert_eq!(def.get("type"), Some(&json!("integer")));
-        Type::Basic("i64".into())
-    } else {
-        translate_type(defs, def)
-    };
     Field {
         doc: def
             .get("description")
             .map(|x| x.as_str().unwrap().to_owned()),
         name: name.to_owned(),
-        ty,
+        ty: translate_type(defs, def),
         required,
+        default: false,
+        deserialize_with: None,
     }
 }
 
@@ -331,7 +1397,7 @@ fn translate_type(defs: &Map<String, Value>, t: &Value) -> Type {
     });
     match ty {
         "integer" => "u64".into(),
-        "number" => "u64".into(),
+        "number" => "f64".into(),
         "boolean" => "bool".into(),
         "string" => {
             let doc = t.get("description").map(|x| x.as_str().unwrap().to_owned());
@@ -405,14 +1471,16 @@ fn is_enum_of<const N: usize>(t: &Value, values: [&str; N]) -> bool {
 }
 
 fn to_snake_case(raw: &str) -> String {
-    if raw == "type" {
-        return "type_".to_owned();
-    }
-    words(raw)
+    let name = words(raw)
         .into_iter()
         .map(|w| format!("_{w}"))
         .collect::<String>()[1..]
-        .to_owned()
+        .to_owned();
+    // Avoid names that are Rust keywords.
+    if name == "type" || name == "continue" {
+        return format!("{name}_");
+    }
+    name
 }
 
 fn to_pascal_case(raw: &str) -> String {
@@ -448,6 +1516,11 @@ fn words(raw: &str) -> Vec<String> {
     result
 }
 
+/// Turns a schema description into doc comment lines.
+///
+/// Schema descriptions put plain lines right after list items, which rustdoc
+/// would treat as continuations of the list item, so a paragraph break is
+/// inserted between them.
 #[derive(Default)]
 struct Writer {
     output: String,
@@ -457,7 +1530,7 @@ struct Writer {
 impl Writer {
     fn check_finish(&mut self) {
         if self.finished_object {
-            self.output.push_str("\n");
+            self.output.push('\n');
             self.finished_object = false;
         }
     }
@@ -465,14 +1538,14 @@ impl Writer {
     fn line(&mut self, line: impl AsRef<str>) {
         self.check_finish();
         self.output.push_str(line.as_ref().trim_end());
-        self.output.push_str("\n");
+        self.output.push('\n');
     }
 
     fn indented(&mut self, line: impl AsRef<str>) {
         self.check_finish();
         self.output.push_str("    ");
         self.output.push_str(line.as_ref().trim_end());
-        self.output.push_str("\n");
+        self.output.push('\n');
     }
 
     fn finished_object(&mut self) {
@@ -491,9 +1564,41 @@ impl Writer {
         }
     }
 
+    /// Writes a match arm, wrapping the expression in a block if it does not fit
+    /// on one line (the same way rustfmt would).
+    fn match_arm(&mut self, depth: usize, pattern: &str, expr: &str) {
+        let indent = "    ".repeat(depth);
+        let line = format!("{indent}{pattern} => {expr},");
+        if line.len() <= 100 {
+            self.line(line);
+        } else {
+            self.line(format!("{indent}{pattern} => {{"));
+            self.line(format!("{indent}    {expr}"));
+            self.line(format!("{indent}}}"));
+        }
+    }
+
+    /// Writes a tuple of string literals followed by a comma, splitting it over
+    /// multiple lines if its contents are longer than 60 characters (the same
+    /// way rustfmt would).
+    fn tuple(&mut self, depth: usize, items: &[&str]) {
+        let indent = "    ".repeat(depth);
+        let items = items.iter().map(|i| format!("{i:?}")).collect::<Vec<_>>();
+        let contents = items.join(", ");
+        if contents.len() <= 60 {
+            self.line(format!("{indent}({contents}),"));
+        } else {
+            self.line(format!("{indent}("));
+            for item in items {
+                self.line(format!("{indent}    {item},"));
+            }
+            self.line(format!("{indent}),"));
+        }
+    }
+
     fn code(&mut self, code: &str) {
         for line in code.lines() {
-            if line == "" {
+            if line.is_empty() {
                 self.finished_object();
                 continue;
             }
@@ -574,6 +1679,10 @@ struct Enum {
 struct Object {
     doc: Option<String>,
     fields: Vec<Field>,
+    /// False for objects containing floats, which can't derive `Eq` and `Hash`.
+    derives_eq: bool,
+    /// Derives added by overrides.
+    derives: Vec<String>,
 }
 
 #[derive(Clone)]
@@ -582,6 +1691,9 @@ struct Field {
     name: String,
     ty: Type,
     required: bool,
+    /// Whether a missing field is deserialized as its default value.
+    default: bool,
+    deserialize_with: Option<String>,
 }
 
 impl ProtocolType {
@@ -600,63 +1712,127 @@ impl ProtocolType {
 impl Object {
     fn write(&self, name: &str, dst: &mut Writer) {
         if let Some(doc) = &self.doc {
-            dst.doc(&doc);
+            dst.doc(doc);
+        }
+        let mut derives = vec!["Debug", "Clone"];
+        derives.extend(self.derives.iter().map(String::as_str));
+        derives.extend(["Deserialize", "Serialize", "PartialEq"]);
+        if self.derives_eq {
+            derives.extend(["Eq", "Hash"]);
         }
-        dst.line("#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]");
+        dst.line(format!("#[derive({})]", derives.join(", ")));
         let mut pending = Vec::new();
-        if self.fields.is_empty() {
-            dst.line(format!("pub struct {} {{}}", name));
-        } else {
-            dst.line(format!("pub struct {} {{", name));
-            for field in &self.fields {
-                let inline_name = format!("{}{}", name, to_pascal_case(&field.name));
-                let mut ty = field.ty.stringify(inline_name, &mut pending);
-                if let Some(doc) = &field.doc {
-                    dst.indented_doc(doc);
-                }
-                dst.indented(format!("#[serde(rename = \"{}\")]", field.name));
-
-                // GDB doesn't always send a threads name so we default to an empty string
-                // Debugpy doesn't send a cwd in a run in terminal request if the client doesn't provide it in the launch request
-                // An empty string indicates that we should use the project's cwd
-                if (name == "Thread" && field.name == "name")
-                    || (name == "RunInTerminalRequestArguments" && field.name == "cwd")
-                {
-                    dst.indented("#[serde(default)]");
-                }
-                if name == "Thread" && field.name == "id" {
-                    ty = "i64".to_string();
-                }
+        dst.line(format!("pub struct {} {{", name));
+        for field in &self.fields {
+            let inline_name = format!("{}{}", name, to_pascal_case(&field.name));
+            let ty = field.ty.stringify(inline_name, &mut pending);
+            if let Some(doc) = &field.doc {
+                dst.indented_doc(doc);
+            }
+            match &field.deserialize_with {
+                Some(deserialize_with) => dst.indented(format!(
+                    "#[serde(rename = \"{}\", deserialize_with = {deserialize_with:?})]",
+                    field.name
+                )),
+                None => dst.indented(format!("#[serde(rename = \"{}\")]", field.name)),
+            }
+            if field.default {
+                dst.indented("#[serde(default)]");
+            }
 
-                let clean_name = to_snake_case(&field.name);
-                if field.required {
-                    dst.indented(format!("pub {}: {},", clean_name, ty));
-                } else {
-                    dst.indented("#[serde(skip_serializing_if = \"Option::is_none\")]");
-                    dst.indented("#[serde(default)]");
-                    dst.indented(format!("pub {}: Option<{}>,", clean_name, ty));
-                }
+            let clean_name = to_snake_case(&field.name);
+            if field.required {
+                dst.indented(format!("pub {}: {},", clean_name, ty));
+            } else {
+                dst.indented("#[serde(skip_serializing_if = \"Option::is_none\")]");
+                dst.indented("#[serde(default)]");
+                dst.indented(format!("pub {}: Option<{}>,", clean_name, ty));
             }
-            dst.line("}");
         }
+        dst.indented_doc("Fields that are not defined by the protocol.");
+        dst.indented("#[cfg(feature = \"extra-fields\")]");
+        dst.indented("#[serde(flatten)]");
+        dst.indented("pub extra: serde_json::Map<String, serde_json::Value>,");
+        dst.line("}");
         dst.finished_object();
+        write_extensible(name, dst);
+        if name == "Capabilities" {
+            self.write_flag_accessors(name, dst);
+        }
         for p in pending {
             p.write(dst);
         }
     }
 
+    /// Writes methods for getting and setting optional boolean fields by their
+    /// protocol name.
+    fn write_flag_accessors(&self, name: &str, dst: &mut Writer) {
+        let flags = self
+            .fields
+            .iter()
+            .filter(|f| !f.required && matches!(&f.ty, Type::Basic(t) if t == "bool"))
+            .map(|f| (f.name.as_str(), to_snake_case(&f.name)))
+            .collect::<Vec<_>>();
+        dst.line(format!("impl {name} {{"));
+        dst.indented_doc("Returns a boolean field by its protocol name, e.g. `supportsStepBack`.\n\nReturns `None` if the field is not set or there is no boolean field with that name.");
+        dst.indented("pub fn flag(&self, name: &str) -> Option<bool> {");
+        dst.indented("    match name {");
+        for (flag, field) in &flags {
+            dst.match_arm(3, &format!("{flag:?}"), &format!("self.{field}"));
+        }
+        dst.indented("        _ => None,");
+        dst.indented("    }");
+        dst.indented("}");
+        dst.finished_object();
+        dst.indented_doc("Sets a boolean field by its protocol name.\n\nReturns false if there is no boolean field with that name.");
+        dst.indented("pub fn set_flag(&mut self, name: &str, value: Option<bool>) -> bool {");
+        dst.indented("    let flag = match name {");
+        for (flag, field) in &flags {
+            dst.match_arm(3, &format!("{flag:?}"), &format!("&mut self.{field}"));
+        }
+        dst.indented("        _ => return false,");
+        dst.indented("    };");
+        dst.indented("    *flag = value;");
+        dst.indented("    true");
+        dst.indented("}");
+        dst.line("}");
+        dst.finished_object();
+    }
+
     fn find_field(&self, name: &str) -> Option<&Field> {
         self.fields.iter().find(|f| f.name == name)
     }
 }
 
+/// Writes the `Extensible` impl giving access to the `extra` field of a struct.
+fn write_extensible(name: &str, dst: &mut Writer) {
+    dst.line("#[cfg(feature = \"extra-fields\")]");
+    dst.line(format!("impl crate::extension::Extensible for {name} {{"));
+    dst.indented("fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {");
+    dst.indented("    &self.extra");
+    dst.indented("}");
+    dst.finished_object();
+    dst.indented("fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {");
+    dst.indented("    &mut self.extra");
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+}
+
 impl Enum {
     fn write(&self, name: &str, dst: &mut Writer) {
         if let Some(doc) = &self.doc {
             dst.doc(doc);
         }
-        if self.exhaustive {
+        let open = !self.exhaustive || name.ends_with("PresentationHint");
+        if !open {
             dst.line("#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy, Deserialize, Serialize, JsonSchema)]");
+        } else if self.exhaustive {
+            dst.line(
+                "#[derive(PartialEq, Eq, Debug, Hash, Clone, Deserialize, Serialize, JsonSchema)]",
+            );
+            // With `extra-fields`, unknown values are kept as a string, which isn't `Copy`.
+            dst.line("#[cfg_attr(not(feature = \"extra-fields\"), derive(Copy))]");
         } else {
             dst.line("#[derive(PartialEq, Eq, Debug, Hash, Clone, Deserialize, Serialize)]");
         }
@@ -674,12 +1850,17 @@ impl Enum {
             dst.indented(format!("{},", to_pascal_case(value)));
         }
         if name.ends_with("StackFramePresentationHint") {
-            dst.indented(format!("#[serde(rename = \"deemphasize\")]"));
+            dst.indented("#[serde(rename = \"deemphasize\")]");
             dst.indented("Deemphasize,");
         }
-        if !self.exhaustive || name.ends_with("PresentationHint") {
+        if open {
+            dst.indented("#[cfg(not(feature = \"extra-fields\"))]");
             dst.indented("#[serde(other)]");
             dst.indented("Unknown,");
+            dst.indented_doc("A value not defined by the protocol.");
+            dst.indented("#[cfg(feature = \"extra-fields\")]");
+            dst.indented("#[serde(untagged)]");
+            dst.indented("Other(String),");
         }
         dst.line("}");
         dst.finished_object();
@@ -756,14 +1937,32 @@ pub struct RestartArguments {
 }
 
 /// Arguments for `Threads` request.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
-pub struct ThreadsArgument {}
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ThreadsArgument {
+    /// Fields that are not defined by the protocol.
+    #[cfg(feature = \"extra-fields\")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(feature = \"extra-fields\")]
+impl crate::extension::Extensible for ThreadsArgument {
+    fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        &mut self.extra
+    }
+}
 ";
 
 const REQUEST_TRAIT: &str = "
 /// Request is a request, with associated command, and argument and response types.
 pub trait Request {
     const COMMAND: &'static str;
+    /// Adapter capability that has to be true for clients to send this request.
+    const REQUIRED_CAPABILITY: Option<&'static str> = None;
     type Arguments: Debug + Clone + Serialize + DeserializeOwned + Send + Sync;
     type Response: Debug + Clone + Serialize + DeserializeOwned + Send + Sync;
 }
@@ -774,5 +1973,97 @@ const EVENT_TRAIT: &str = "
 pub trait Event {
     const EVENT: &'static str;
     type Body: Debug + Clone + Serialize + DeserializeOwned + Send + Sync;
+
+    /// Wraps a body of this event in the matching [`Events`] variant.
+    fn into_events(body: Self::Body) -> Events;
+
+    /// Returns the body if `events` is this event.
+    fn from_events(events: &Events) -> Option<&Self::Body>;
+}
+";
+
+const ADAPTER_PRELUDE: &str = "
+/// Error returned by a [`DebugAdapter`] request handler, sent to the client as an error response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdapterError {
+    /// Short error reason, or a free-form message.
+    pub message: String,
+    /// Structured error for the response body.
+    pub error: Option<Box<crate::Message>>,
+}
+
+/// Result of a [`DebugAdapter`] request handler.
+pub type HandlerResult<T> = Result<T, AdapterError>;
+
+impl AdapterError {
+    pub fn new(message: impl Into<String>) -> Self {
+        AdapterError {
+            message: message.into(),
+            error: None,
+        }
+    }
+
+    pub fn with_error(message: impl Into<String>, error: crate::Message) -> Self {
+        AdapterError {
+            message: message.into(),
+            error: Some(Box::new(error)),
+        }
+    }
+
+    /// Error for a request that the adapter does not implement.
+    pub fn unsupported(command: &str) -> Self {
+        AdapterError::new(format!(\"unsupported request: {command}\"))
+    }
+
+    /// Error for a request whose arguments could not be decoded.
+    pub fn invalid_arguments(error: serde_json::Error) -> Self {
+        AdapterError::new(format!(\"invalid arguments: {error}\"))
+    }
+
+    /// Creates an error response to `request`.
+    pub fn into_response(self, request: &Request) -> Response {
+        Response::error(
+            0,
+            request.seq,
+            request.command.clone(),
+            self.message,
+            self.error.map(|e| *e),
+        )
+    }
+}
+
+impl std::fmt::Display for AdapterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, \"{}\", self.message)
+    }
+}
+
+impl std::error::Error for AdapterError {}
+
+pub(crate) fn respond<R: requests::Request>(
+    request: &Request,
+    result: HandlerResult<R::Response>,
+) -> Response {
+    let body = match result {
+        Ok(body) => body,
+        Err(e) => return e.into_response(request),
+    };
+    match Response::success::<R>(0, request.seq, body) {
+        Ok(response) => response,
+        Err(e) => {
+            AdapterError::new(format!(\"failed to encode response: {e}\")).into_response(request)
+        }
+    }
+}
+
+/// Error returned by the default handler of a request, which warns if adapter
+/// `A` claims to handle the request.
+fn not_implemented<A: DebugAdapter + ?Sized>(command: &str) -> AdapterError {
+    if A::HANDLED_REQUESTS.contains(&command) {
+        log::warn!(
+            \"`{command}` is listed in `DebugAdapter::HANDLED_REQUESTS`, but its handler is not implemented\"
+        );
+    }
+    AdapterError::unsupported(command)
 }
 ";
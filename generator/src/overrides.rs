@@ -0,0 +1,303 @@
+use std::cell::Cell;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::{ProtocolType, Type};
+
+/// Workarounds for adapters that don't follow the schema, read from
+/// `generator/overrides.json`.
+///
+/// Field overrides apply to fields named `field` of the object at path `type`,
+/// like `StackFrame` or `StoppedEvent.body`, or of every object if there is
+/// no `type`. They can set:
+///
+/// - `rust_type`: Rust type of the field, instead of the one from the schema,
+/// - `schema_type`: Rust type that the schema gives the field, checked before
+///   it is replaced with `rust_type`,
+/// - `deserialize_with`: path of a function deserializing the field, like
+///   `crate::negative_to_zero`,
+/// - `default`: whether a missing field is deserialized as its default value.
+///
+/// Type overrides add `derives` to the struct generated for a path. `note`
+/// explains an override and is ignored. Every override has to apply to some
+/// field or type, see [`Overrides::check_used`].
+#[derive(Default)]
+pub struct Overrides {
+    fields: Vec<FieldOverride>,
+    derives: Vec<TypeOverride>,
+}
+
+struct FieldOverride {
+    path: Option<String>,
+    field: String,
+    rust_type: Option<String>,
+    schema_type: Option<String>,
+    deserialize_with: Option<String>,
+    default: bool,
+    used: Cell<bool>,
+}
+
+struct TypeOverride {
+    path: String,
+    derives: Vec<String>,
+    used: Cell<bool>,
+}
+
+impl Overrides {
+    pub fn load(path: &Path) -> Self {
+        let contents = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+        let value = serde_json::from_str(&contents)
+            .unwrap_or_else(|e| panic!("invalid overrides {}: {e}", path.display()));
+        Self::parse(&value).unwrap_or_else(|e| panic!("invalid overrides {}: {e}", path.display()))
+    }
+
+    fn parse(value: &Value) -> Result<Self, String> {
+        let root = object(value, "overrides", &["fields", "types"])?;
+        let mut overrides = Overrides::default();
+        for field in root
+            .get("fields")
+            .map_or(Ok(&[][..]), |f| array(f, "fields"))?
+        {
+            let field = object(
+                field,
+                "field override",
+                &[
+                    "type",
+                    "field",
+                    "rust_type",
+                    "schema_type",
+                    "deserialize_with",
+                    "default",
+                    "note",
+                ],
+            )?;
+            overrides.fields.push(FieldOverride {
+                path: optional_string(field, "type")?,
+                field: optional_string(field, "field")?.ok_or("field override without `field`")?,
+                rust_type: optional_string(field, "rust_type")?,
+                schema_type: optional_string(field, "schema_type")?,
+                deserialize_with: optional_string(field, "deserialize_with")?,
+                default: match field.get("default") {
+                    None => false,
+                    Some(default) => default.as_bool().ok_or("`default` is not a boolean")?,
+                },
+                used: Cell::new(false),
+            });
+        }
+        if let Some(types) = root.get("types") {
+            for (path, ty) in object(types, "types", &[])? {
+                let ty = object(ty, "type override", &["derives", "note"])?;
+                let derives = match ty.get("derives") {
+                    None => Vec::new(),
+                    Some(derives) => array(derives, "derives")?
+                        .iter()
+                        .map(|d| d.as_str().map(str::to_owned))
+                        .collect::<Option<_>>()
+                        .ok_or("`derives` is not a list of strings")?,
+                };
+                overrides.derives.push(TypeOverride {
+                    path: path.clone(),
+                    derives,
+                    used: Cell::new(false),
+                });
+            }
+        }
+        Ok(overrides)
+    }
+
+    /// Applies the overrides to types generated from a schema.
+    pub fn apply(&self, types: &mut [ProtocolType]) {
+        for ty in types {
+            self.apply_to(&ty.name, &mut ty.ty);
+        }
+    }
+
+    fn apply_to(&self, path: &str, ty: &mut Type) {
+        match ty {
+            Type::Object(o) => {
+                for ty in self.derives.iter().filter(|t| t.path == path) {
+                    o.derives.extend(ty.derives.iter().cloned());
+                    ty.used.set(true);
+                }
+                for field in &mut o.fields {
+                    let field_path = format!("{path}.{}", field.name);
+                    self.apply_to(&field_path, &mut field.ty);
+                    let overrides = self.fields.iter().filter(|f| {
+                        f.field == field.name && f.path.as_ref().is_none_or(|p| p == path)
+                    });
+                    for o in overrides {
+                        if let Some(schema_type) = &o.schema_type {
+                            let ty = field.ty.stringify(String::new(), &mut Vec::new());
+                            assert_eq!(
+                                &ty, schema_type,
+                                "schema type of overridden field {field_path} changed"
+                            );
+                        }
+                        if let Some(rust_type) = &o.rust_type {
+                            field.ty = Type::Basic(rust_type.clone());
+                        }
+                        if let Some(deserialize_with) = &o.deserialize_with {
+                            field.deserialize_with = Some(deserialize_with.clone());
+                        }
+                        field.default |= o.default;
+                        o.used.set(true);
+                    }
+                }
+            }
+            Type::Vec(ty) | Type::Option(ty) => self.apply_to(path, ty),
+            Type::Any | Type::Basic(_) | Type::Enum(_) => {}
+        }
+    }
+
+    /// Panics if an override did not apply to any of the types it was applied
+    /// to, which means that it is misspelled or the schema no longer needs it.
+    pub fn check_used(&self) {
+        let unused = self
+            .fields
+            .iter()
+            .filter(|f| !f.used.get())
+            .map(|f| match &f.path {
+                Some(path) => format!("field {path}.{}", f.field),
+                None => format!("field {}", f.field),
+            })
+            .chain(
+                self.derives
+                    .iter()
+                    .filter(|t| !t.used.get())
+                    .map(|t| format!("type {}", t.path)),
+            )
+            .collect::<Vec<_>>();
+        assert!(unused.is_empty(), "unused overrides: {}", unused.join(", "));
+    }
+}
+
+/// Returns `value` as an object, checking that it only has `keys` if they are
+/// given.
+fn object<'a>(
+    value: &'a Value,
+    what: &str,
+    keys: &[&str],
+) -> Result<&'a Map<String, Value>, String> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| format!("{what} is not an object"))?;
+    if let Some(key) = object
+        .keys()
+        .find(|k| !keys.is_empty() && !keys.contains(&k.as_str()))
+    {
+        return Err(format!("unknown key `{key}` in {what}"));
+    }
+    Ok(object)
+}
+
+fn array<'a>(value: &'a Value, what: &str) -> Result<&'a [Value], String> {
+    value
+        .as_array()
+        .map(Vec::as_slice)
+        .ok_or_else(|| format!("`{what}` is not a list"))
+}
+
+fn optional_string(object: &Map<String, Value>, key: &str) -> Result<Option<String>, String> {
+    match object.get(key) {
+        None => Ok(None),
+        Some(value) => match value.as_str() {
+            Some(s) => Ok(Some(s.to_owned())),
+            None => Err(format!("`{key}` is not a string")),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_protocol_types, write_types};
+    use serde_json::json;
+
+    #[test]
+    fn overrides_fields_by_path() {
+        let overrides = Overrides::parse(&json!({
+            "fields": [
+                { "field": "id", "rust_type": "i64" },
+                { "type": "Frame.source", "field": "line", "deserialize_with": "crate::negative_to_zero" },
+                { "type": "Frame", "field": "name", "default": true, "note": "Sometimes missing." },
+            ],
+            "types": { "Frame": { "derives": ["Default"] } },
+        }))
+        .unwrap();
+        let object = |properties: Value| json!({ "type": "object", "properties": properties });
+        let schema = json!({
+            "definitions": {
+                "Frame": object(json!({
+                    "id": { "type": "integer" },
+                    "name": { "type": "string" },
+                    "line": { "type": "integer" },
+                    "source": {
+                        "type": "object",
+                        "properties": { "line": { "type": "integer" } },
+                        "required": ["line"],
+                    },
+                })),
+            },
+        });
+        let types = write_types(&generate_protocol_types(&schema, &overrides));
+        assert!(types.contains("#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]\npub struct Frame {"));
+        assert!(types.contains("pub id: Option<i64>,"));
+        assert!(types.contains("#[serde(rename = \"name\")]\n    #[serde(default)]\n"));
+        assert_eq!(types.matches("deserialize_with").count(), 1);
+        assert!(types.contains("pub struct FrameSource {\n    #[serde(rename = \"line\", deserialize_with = \"crate::negative_to_zero\")]"));
+        overrides.check_used();
+    }
+
+    #[test]
+    #[should_panic(expected = "unused overrides: field Frame.lines, type Framee")]
+    fn panics_on_unused_overrides() {
+        let overrides = Overrides::parse(&json!({
+            "fields": [
+                { "field": "id", "rust_type": "i64" },
+                { "type": "Frame", "field": "lines", "default": true },
+            ],
+            "types": { "Framee": { "derives": ["Default"] } },
+        }))
+        .unwrap();
+        let schema = json!({
+            "definitions": {
+                "Frame": { "type": "object", "properties": { "id": { "type": "integer" } } },
+            },
+        });
+        generate_protocol_types(&schema, &overrides);
+        overrides.check_used();
+    }
+
+    #[test]
+    #[should_panic(expected = "schema type of overridden field Frame.id changed")]
+    fn checks_schema_types() {
+        let overrides = Overrides::parse(&json!({
+            "fields": [{ "field": "id", "rust_type": "i64", "schema_type": "String" }],
+        }))
+        .unwrap();
+        let schema = json!({
+            "definitions": {
+                "Frame": { "type": "object", "properties": { "id": { "type": "integer" } } },
+            },
+        });
+        generate_protocol_types(&schema, &overrides);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let error = Overrides::parse(&json!({
+            "fields": [{ "field": "id", "rust-type": "i64" }],
+        }));
+        assert_eq!(
+            error.err().as_deref(),
+            Some("unknown key `rust-type` in field override")
+        );
+        let error = Overrides::parse(&json!({ "fields": [{ "type": "Thread" }] }));
+        assert_eq!(
+            error.err().as_deref(),
+            Some("field override without `field`")
+        );
+    }
+}